@@ -0,0 +1,67 @@
+//! Instruction throughput benchmark for the CHIP-8 core.
+//!
+//! Runs a small ROM that mixes random numbers, arithmetic, sprite drawing and
+//! jumps for a fixed number of cycles, and reports the throughput in
+//! instructions per second. The RNG is seeded so every run executes exactly
+//! the same instructions.
+//!
+//! Run it with:
+//!
+//! ```sh
+//! cargo bench -p chip8_core
+//! ```
+//!
+//! Criterion keeps the previous results under `target/criterion` and reports
+//! the change against them, which makes regressions easy to spot.
+
+use std::hint::black_box;
+
+use chip8_core::Chip8;
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+
+/// Number of instructions executed per benchmark iteration.
+const CYCLES: u64 = 10_000;
+
+/// Seed for the machine's RNG, fixed so runs are comparable.
+const RNG_SEED: u64 = 0xC8;
+
+#[rustfmt::skip]
+const ROM: [u8; 27] = [
+    0x60, 0x00, // 0x200: LD V0, 0x00
+    0x61, 0x00, // 0x202: LD V1, 0x00
+    0xA2, 0x16, // 0x204: LD I, 0x216
+    0xC2, 0xFF, // 0x206: RND V2, 0xFF
+    0x80, 0x24, // 0x208: ADD V0, V2
+    0x71, 0x01, // 0x20A: ADD V1, 0x01
+    0xD0, 0x15, // 0x20C: DRW V0, V1, 5
+    0x31, 0x40, // 0x20E: SE V1, 0x40
+    0x12, 0x06, // 0x210: JP 0x206
+    0x61, 0x00, // 0x212: LD V1, 0x00
+    0x12, 0x06, // 0x214: JP 0x206
+    0xF0, 0x90, 0x90, 0x90, 0xF0, // 0x216: sprite
+];
+
+fn throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chip8_core");
+    group.throughput(Throughput::Elements(CYCLES));
+    group.bench_function("run", |b| {
+        b.iter_batched_ref(
+            || {
+                let mut chip8 = Chip8::builder().rng_seed(RNG_SEED).build().unwrap();
+                chip8.load_rom(&ROM).unwrap();
+                chip8
+            },
+            |chip8| {
+                for _ in 0..CYCLES {
+                    chip8.run().unwrap();
+                }
+                black_box(chip8.framebuffer());
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
@@ -0,0 +1,118 @@
+//! Builder for configuring a CHIP-8 virtual machine before construction.
+//!
+//! [`Chip8::new()`] creates a machine with the default configuration. The
+//! [`Chip8Builder`] allows overriding that configuration, e.g. for debugging
+//! or for ROMs that expect non-standard behavior.
+
+use crate::{Chip8, Chip8Error};
+
+/// Builder for a [`Chip8`] machine with a non-default configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use chip8_core::Chip8;
+///
+/// let chip8 = Chip8::builder().fill_pattern(0xFF).build().unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Chip8Builder {
+    fill_pattern: u8,
+    initial_hires: bool,
+    rng_seed: Option<u64>,
+    protect_font: bool,
+    warn_on_odd_pc: bool,
+    font: Option<[u8; 80]>,
+}
+
+impl Chip8Builder {
+    /// Creates a builder with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the byte used to fill RAM and the general-purpose registers.
+    ///
+    /// Real hardware does not guarantee zeroed memory. Filling it with a
+    /// recognizable pattern surfaces ROM bugs that read uninitialized memory
+    /// or registers. The font region is never filled, and the ROM overwrites
+    /// its own region when loaded. The pattern is reapplied on
+    /// [`Chip8::reset()`]. Defaults to `0`.
+    pub fn fill_pattern(mut self, pattern: u8) -> Self {
+        self.fill_pattern = pattern;
+        self
+    }
+
+    /// Sets whether the machine boots in the 128x64 high resolution mode.
+    ///
+    /// Some SUPER-CHIP ROMs assume the high resolution mode from the start and
+    /// never switch to it themselves. The mode is restored on
+    /// [`Chip8::reset()`]. Defaults to `false` (64x32).
+    pub fn initial_hires(mut self, hires: bool) -> Self {
+        self.initial_hires = hires;
+        self
+    }
+
+    /// Seeds the random number generator used by `CXNN`.
+    ///
+    /// A seeded machine produces the same sequence of random numbers on every
+    /// run, which makes benchmarks, tests and input replays reproducible. The
+    /// seed is reapplied on [`Chip8::reset()`]. By default the generator is
+    /// seeded from the operating system.
+    pub fn rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Sets whether the built-in font set is write-protected.
+    ///
+    /// When enabled, instructions writing into the font region fail with
+    /// `MemoryError::WriteProtected` instead of corrupting the digit sprites.
+    /// See [`Memory::protect_font()`](crate::Memory::protect_font). The
+    /// protection is reapplied on [`Chip8::reset()`]. Defaults to `false`.
+    pub fn protect_font(mut self, protect: bool) -> Self {
+        self.protect_font = protect;
+        self
+    }
+
+    /// Sets whether instruction fetches from odd addresses are recorded.
+    ///
+    /// Fetching from an odd address is valid, but usually means a ROM jumped
+    /// into the middle of an instruction or into data. When enabled, each
+    /// distinct odd PC is recorded before the fetch and can be read with
+    /// [`Chip8::odd_pc_warnings()`]. Execution is not affected. Defaults to
+    /// `false`.
+    pub fn warn_on_odd_pc(mut self, warn: bool) -> Self {
+        self.warn_on_odd_pc = warn;
+        self
+    }
+
+    /// Replaces the built-in font set with custom digit sprites.
+    ///
+    /// `font` holds the 16 sprites for the hex digits 0-F, 5 bytes each, and
+    /// is loaded in place of the built-in font, so `FX29` selects its
+    /// sprites. See [`Memory::set_font()`](crate::Memory::set_font). The font
+    /// is reloaded on [`Chip8::reset()`]. Defaults to the standard font.
+    pub fn font(mut self, font: &[u8; 80]) -> Self {
+        self.font = Some(*font);
+        self
+    }
+
+    /// Builds the configured machine.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Chip8)` with a new, ready-to-use `Chip8` instance.
+    /// * `Err(Chip8Error)` if the machine cannot be initialized.
+    pub fn build(self) -> Result<Chip8, Chip8Error> {
+        let mut chip8 = Chip8::new()?;
+        chip8.fill_pattern = self.fill_pattern;
+        chip8.initial_hires = self.initial_hires;
+        chip8.rng_seed = self.rng_seed;
+        chip8.protect_font = self.protect_font;
+        chip8.warn_on_odd_pc = self.warn_on_odd_pc;
+        chip8.font = self.font;
+        chip8.reset()?;
+        Ok(chip8)
+    }
+}
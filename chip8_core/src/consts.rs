@@ -3,3 +3,12 @@ pub const ROM_START_ADDRESS: usize = 0x200;
 
 pub const FRAMEBUFFER_WIDTH: usize = 64;
 pub const FRAMEBUFFER_HEIGHT: usize = 32;
+
+pub const HIRES_FRAMEBUFFER_WIDTH: usize = 128;
+pub const HIRES_FRAMEBUFFER_HEIGHT: usize = 64;
+
+/// XO-CHIP `F000 NNNN` opcode, the only instruction that is 4 bytes long
+pub const LONG_INSTRUCTION_OPCODE: u16 = 0xF000;
+
+/// Number of instructions executed between two clock reads in deadline-bound runs
+pub const DEADLINE_CHECK_INTERVAL: usize = 1024;
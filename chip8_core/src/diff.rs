@@ -0,0 +1,526 @@
+//! State deltas between CHIP-8 machines.
+//!
+//! This module computes compact differences between the runtime state of two
+//! machines, e.g. to keep network peers in sync by sending only what changed
+//! since a shared baseline instead of a full copy of the machine. Diffs can
+//! be encoded to bytes with [`StateDiff::to_bytes()`] to cross process or
+//! network boundaries.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::consts::{HIRES_FRAMEBUFFER_HEIGHT, HIRES_FRAMEBUFFER_WIDTH};
+use crate::memory::RAM_SIZE;
+use crate::{Chip8, Chip8Error};
+
+/// Version of the byte encoding produced by [`StateDiff::to_bytes()`]
+const ENCODING_VERSION: u8 = 1;
+
+/// Maximum number of random draws [`Chip8::apply_diff()`] replays to sync the generator
+const MAX_REPLAYED_DRAWS: u64 = 1 << 20;
+
+/// The difference between the runtime state of two [`Chip8`] machines.
+///
+/// Registers, memory and the framebuffer are stored sparsely, as the values
+/// that changed. The remaining, small parts of the state (index register,
+/// program counter, stack, timers, keypad and flags) are always included.
+///
+/// The random number generator is kept in sync through its seed and the
+/// number of bytes drawn from it, so seeded machines (see
+/// [`Chip8Builder::rng_seed()`](crate::Chip8Builder::rng_seed)) keep drawing
+/// the same bytes after the diff is applied.
+///
+/// Created with [`Chip8::state_diff()`] and applied with [`Chip8::apply_diff()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Changed general-purpose registers as `(index, value)`
+    registers: Vec<(usize, u8)>,
+    /// Runs of changed memory as `(start address, bytes)`
+    memory: Vec<(usize, Vec<u8>)>,
+    /// Runs of changed pixels as `(start index, pixels)`
+    framebuffer: Vec<(usize, Vec<u8>)>,
+    i: u16,
+    pc: u16,
+    sp: u8,
+    stack: [u16; 16],
+    dt: u8,
+    st: u8,
+    hires: bool,
+    keyboard: [u8; 16],
+    display_updated: bool,
+    waiting_for_key: Option<usize>,
+    halted: bool,
+    last_draw_collided: bool,
+    collision_count: u64,
+    cycle_count: u64,
+    random_draws: u64,
+    rng_seed: Option<u64>,
+    rom_len: usize,
+    high_water_mark: usize,
+}
+
+impl StateDiff {
+    /// Returns the number of changed registers, memory bytes and pixels.
+    ///
+    /// This gives an idea of the size of the diff; the always-included parts
+    /// of the state are not counted.
+    pub fn changed_count(&self) -> usize {
+        let runs = |runs: &[(usize, Vec<u8>)]| -> usize {
+            runs.iter().map(|(_, bytes)| bytes.len()).sum()
+        };
+        self.registers.len() + runs(&self.memory) + runs(&self.framebuffer)
+    }
+
+    /// Encodes the diff as bytes, e.g. to send it to a network peer.
+    ///
+    /// The encoding is versioned and little-endian; decode it with
+    /// [`StateDiff::from_bytes()`].
+    ///
+    /// # Returns
+    ///
+    /// The encoded diff.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![ENCODING_VERSION];
+
+        out.push(self.registers.len() as u8);
+        for &(index, value) in &self.registers {
+            out.extend([index as u8, value]);
+        }
+        for runs in [&self.memory, &self.framebuffer] {
+            out.extend((runs.len() as u32).to_le_bytes());
+            for (start, bytes) in runs {
+                out.extend((*start as u32).to_le_bytes());
+                out.extend((bytes.len() as u32).to_le_bytes());
+                out.extend(bytes);
+            }
+        }
+
+        out.extend(self.i.to_le_bytes());
+        out.extend(self.pc.to_le_bytes());
+        out.push(self.sp);
+        for entry in self.stack {
+            out.extend(entry.to_le_bytes());
+        }
+        out.extend([self.dt, self.st, self.hires as u8]);
+        out.extend(self.keyboard);
+        out.push(self.display_updated as u8);
+        match self.waiting_for_key {
+            Some(x) => out.extend([1, x as u8]),
+            None => out.push(0),
+        }
+        out.extend([self.halted as u8, self.last_draw_collided as u8]);
+        out.extend(self.collision_count.to_le_bytes());
+        out.extend(self.cycle_count.to_le_bytes());
+        out.extend(self.random_draws.to_le_bytes());
+        match self.rng_seed {
+            Some(seed) => {
+                out.push(1);
+                out.extend(seed.to_le_bytes());
+            }
+            None => out.push(0),
+        }
+        out.extend((self.rom_len as u32).to_le_bytes());
+        out.extend((self.high_water_mark as u32).to_le_bytes());
+        out
+    }
+
+    /// Decodes a diff encoded with [`StateDiff::to_bytes()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes`: The encoded diff.
+    ///
+    /// # Returns
+    ///
+    /// The decoded [`StateDiff`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::InvalidStateDiff` if the bytes are truncated, have
+    /// trailing data, use an unknown encoding version, or describe registers,
+    /// memory or pixels outside the machine.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Chip8Error> {
+        let mut reader = Reader { bytes };
+        if reader.u8()? != ENCODING_VERSION {
+            return Err(Chip8Error::InvalidStateDiff("unknown encoding version"));
+        }
+
+        let mut registers = Vec::new();
+        for _ in 0..reader.u8()? {
+            let index = reader.u8()? as usize;
+            if index >= 16 {
+                return Err(Chip8Error::InvalidStateDiff("register out of range"));
+            }
+            registers.push((index, reader.u8()?));
+        }
+        let memory = reader.runs(RAM_SIZE)?;
+        let framebuffer = reader.runs(HIRES_FRAMEBUFFER_WIDTH * HIRES_FRAMEBUFFER_HEIGHT)?;
+
+        let i = reader.u16()?;
+        let pc = reader.u16()?;
+        let sp = reader.u8()?;
+        let mut stack = [0; 16];
+        for entry in &mut stack {
+            *entry = reader.u16()?;
+        }
+        let dt = reader.u8()?;
+        let st = reader.u8()?;
+        let hires = reader.bool()?;
+        let mut keyboard = [0; 16];
+        keyboard.copy_from_slice(reader.take(16)?);
+        let display_updated = reader.bool()?;
+        let waiting_for_key = match reader.bool()? {
+            true => match reader.u8()? as usize {
+                x if x < 16 => Some(x),
+                _ => return Err(Chip8Error::InvalidStateDiff("register out of range")),
+            },
+            false => None,
+        };
+        let halted = reader.bool()?;
+        let last_draw_collided = reader.bool()?;
+        let collision_count = reader.u64()?;
+        let cycle_count = reader.u64()?;
+        let random_draws = reader.u64()?;
+        let rng_seed = match reader.bool()? {
+            true => Some(reader.u64()?),
+            false => None,
+        };
+        let rom_len = reader.u32()? as usize;
+        let high_water_mark = reader.u32()? as usize;
+
+        if !reader.bytes.is_empty() {
+            return Err(Chip8Error::InvalidStateDiff("trailing data"));
+        }
+        Ok(StateDiff {
+            registers,
+            memory,
+            framebuffer,
+            i,
+            pc,
+            sp,
+            stack,
+            dt,
+            st,
+            hires,
+            keyboard,
+            display_updated,
+            waiting_for_key,
+            halted,
+            last_draw_collided,
+            collision_count,
+            cycle_count,
+            random_draws,
+            rng_seed,
+            rom_len,
+            high_water_mark,
+        })
+    }
+}
+
+/// Cursor over the bytes decoded by [`StateDiff::from_bytes()`].
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Chip8Error> {
+        if self.bytes.len() < len {
+            return Err(Chip8Error::InvalidStateDiff("truncated"));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, Chip8Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, Chip8Error> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Chip8Error::InvalidStateDiff("invalid flag")),
+        }
+    }
+
+    fn u16(&mut self) -> Result<u16, Chip8Error> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, Chip8Error> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, Chip8Error> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// Reads runs of bytes, checking that they fit in `size` bytes.
+    fn runs(&mut self, size: usize) -> Result<Vec<(usize, Vec<u8>)>, Chip8Error> {
+        let mut runs = Vec::new();
+        for _ in 0..self.u32()? {
+            let start = self.u32()? as usize;
+            let len = self.u32()? as usize;
+            if start.checked_add(len).is_none_or(|end| end > size) {
+                return Err(Chip8Error::InvalidStateDiff("run out of range"));
+            }
+            runs.push((start, self.take(len)?.to_vec()));
+        }
+        Ok(runs)
+    }
+}
+
+impl Chip8 {
+    /// Computes the difference between this machine and a baseline.
+    ///
+    /// Applying the result to a machine in the same state as `baseline` with
+    /// [`Chip8::apply_diff()`] brings it to the state of `self`. Only the
+    /// runtime state is compared; configuration such as quirks or hooks is not.
+    ///
+    /// # Arguments
+    ///
+    /// * `baseline`: The machine state the diff is relative to.
+    ///
+    /// # Returns
+    ///
+    /// A [`StateDiff`] describing how to go from `baseline` to `self`.
+    pub fn state_diff(&self, baseline: &Chip8) -> StateDiff {
+        let registers = self
+            .registers
+            .iter()
+            .zip(baseline.registers.iter())
+            .enumerate()
+            .filter(|(_, (new, old))| new != old)
+            .map(|(index, (&new, _))| (index, new))
+            .collect();
+
+        StateDiff {
+            registers,
+            memory: changed_runs(
+                self.memory.get(..).unwrap_or_default(),
+                baseline.memory.get(..).unwrap_or_default(),
+            ),
+            framebuffer: changed_runs(&self.framebuffer, &baseline.framebuffer),
+            i: self.i,
+            pc: self.pc,
+            sp: self.sp,
+            stack: self.stack,
+            dt: self.dt,
+            st: self.st,
+            hires: self.hires,
+            keyboard: self.keyboard,
+            display_updated: self.display_updated,
+            waiting_for_key: self.waiting_for_key,
+            halted: self.halted,
+            last_draw_collided: self.last_draw_collided,
+            collision_count: self.collision_count,
+            cycle_count: self.cycle_count,
+            random_draws: self.random_draws,
+            rng_seed: self.rng_seed,
+            rom_len: self.rom_len,
+            high_water_mark: self.memory.high_water_mark(),
+        }
+    }
+
+    /// Applies a diff computed with [`Chip8::state_diff()`].
+    ///
+    /// The machine must be in the same state as the baseline the diff was
+    /// computed against; otherwise only the changed parts are overwritten and
+    /// the result is a mix of both states. Read-only memory ranges don't apply
+    /// to the diff.
+    ///
+    /// If the source machine was seeded, this machine takes over its seed and
+    /// replays the random draws it made, so both generators end in the same
+    /// state. Replaying costs one draw per byte the source drew since this
+    /// machine's generator was last in sync, or since the seed if it never
+    /// was, and is limited to 2^20 draws.
+    ///
+    /// # Arguments
+    ///
+    /// * `diff`: The diff to apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::InvalidStateDiff` if more than 2^20 random draws
+    /// would have to be replayed. The machine is left unchanged.
+    pub fn apply_diff(&mut self, diff: &StateDiff) -> Result<(), Chip8Error> {
+        // Reseed unless the generator is already partway along the same sequence
+        let reseed = diff.rng_seed.is_some()
+            && (self.rng_seed != diff.rng_seed || self.random_draws > diff.random_draws);
+        if diff.rng_seed.is_some() {
+            let replayed_from = if reseed { 0 } else { self.random_draws };
+            if diff.random_draws - replayed_from > MAX_REPLAYED_DRAWS {
+                return Err(Chip8Error::InvalidStateDiff(
+                    "too many random draws to replay",
+                ));
+            }
+        }
+
+        for &(index, value) in &diff.registers {
+            self.registers[index] = value;
+        }
+        for (start, bytes) in &diff.memory {
+            self.memory.patch(*start, bytes);
+        }
+        for (start, pixels) in &diff.framebuffer {
+            self.framebuffer[*start..*start + pixels.len()].copy_from_slice(pixels);
+        }
+        self.memory.set_high_water_mark(diff.high_water_mark);
+
+        self.i = diff.i;
+        self.pc = diff.pc;
+        self.sp = diff.sp;
+        self.stack = diff.stack;
+        self.dt = diff.dt;
+        self.st = diff.st;
+        self.hires = diff.hires;
+        self.keyboard = diff.keyboard;
+        self.display_updated = diff.display_updated;
+        self.waiting_for_key = diff.waiting_for_key;
+        self.halted = diff.halted;
+        self.last_draw_collided = diff.last_draw_collided;
+        self.collision_count = diff.collision_count;
+        self.cycle_count = diff.cycle_count;
+        if let Some(seed) = diff.rng_seed {
+            if reseed {
+                self.rng_seed = Some(seed);
+                self.rng = StdRng::seed_from_u64(seed);
+                self.random_draws = 0;
+            }
+            for _ in self.random_draws..diff.random_draws {
+                // Must draw the same way as CXNN
+                let _: u8 = self.rng.random_range(0..=255);
+            }
+        }
+        self.random_draws = diff.random_draws;
+        self.rom_len = diff.rom_len;
+        Ok(())
+    }
+}
+
+/// Returns the runs of consecutive bytes where `new` differs from `old`.
+fn changed_runs(new: &[u8], old: &[u8]) -> Vec<(usize, Vec<u8>)> {
+    let mut runs: Vec<(usize, Vec<u8>)> = Vec::new();
+    for (index, (&n, &o)) in new.iter().zip(old.iter()).enumerate() {
+        if n == o {
+            continue;
+        }
+        match runs.last_mut() {
+            Some((start, bytes)) if *start + bytes.len() == index => bytes.push(n),
+            _ => runs.push((index, vec![n])),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_diff_apply() {
+        // LD V0, 0x42; LD I, 0x300; LD [I], V0; LD F, V0; DRW V0, V0, 5; LD DT, V0
+        let rom = [
+            0x60, 0x42, 0xA3, 0x00, 0xF0, 0x55, 0xF0, 0x29, 0xD0, 0x05, 0xF0, 0x15,
+        ];
+        let mut baseline = Chip8::with_rom(&rom).unwrap();
+        let mut peer = Chip8::with_rom(&rom).unwrap();
+        baseline.run().unwrap();
+        peer.run().unwrap();
+
+        for _ in 0..5 {
+            baseline.run().unwrap();
+        }
+        let diff = baseline.state_diff(&peer);
+        assert!(diff.changed_count() > 0);
+
+        peer.apply_diff(&diff).unwrap();
+        assert!(peer.snapshot() == baseline.snapshot());
+        assert_eq!(baseline.state_diff(&peer).changed_count(), 0);
+    }
+
+    #[test]
+    fn test_changed_runs() {
+        let old = [0, 0, 0, 0, 0, 0];
+        let new = [1, 2, 0, 0, 3, 0];
+        assert_eq!(
+            changed_runs(&new, &old),
+            vec![(0, vec![1, 2]), (4, vec![3])]
+        );
+    }
+
+    #[test]
+    fn test_state_diff_bytes_round_trip() {
+        // LD V0, 0x42; LD I, 0x300; LD [I], V0; LD F, V0; DRW V0, V0, 5; LD DT, V0
+        let rom = [
+            0x60, 0x42, 0xA3, 0x00, 0xF0, 0x55, 0xF0, 0x29, 0xD0, 0x05, 0xF0, 0x15,
+        ];
+        let baseline = Chip8::with_rom(&rom).unwrap();
+        let mut chip8 = Chip8::with_rom(&rom).unwrap();
+        for _ in 0..6 {
+            chip8.run().unwrap();
+        }
+
+        let diff = chip8.state_diff(&baseline);
+        let bytes = diff.to_bytes();
+        assert_eq!(StateDiff::from_bytes(&bytes).unwrap(), diff);
+
+        assert!(matches!(
+            StateDiff::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Chip8Error::InvalidStateDiff(_))
+        ));
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(StateDiff::from_bytes(&trailing).is_err());
+        let mut bad_version = bytes;
+        bad_version[0] = ENCODING_VERSION + 1;
+        assert!(StateDiff::from_bytes(&bad_version).is_err());
+    }
+
+    #[test]
+    fn test_state_diff_syncs_rng() {
+        // RND V0, 0xFF; JP 0x200
+        let rom = [0xC0, 0xFF, 0x12, 0x00];
+        let mut source = Chip8::builder().rng_seed(7).build().unwrap();
+        source.load_rom(&rom).unwrap();
+        let mut peer = Chip8::with_rom(&rom).unwrap();
+        let baseline = Chip8::with_rom(&rom).unwrap();
+
+        for _ in 0..10 {
+            source.run().unwrap();
+        }
+        let diff = StateDiff::from_bytes(&source.state_diff(&baseline).to_bytes()).unwrap();
+        peer.apply_diff(&diff).unwrap();
+        assert_eq!(peer.random_draws(), source.random_draws());
+
+        // Both generators continue with the same bytes
+        for _ in 0..10 {
+            source.run().unwrap();
+            peer.run().unwrap();
+            assert_eq!(peer.registers[0], source.registers[0]);
+        }
+    }
+
+    #[test]
+    fn test_state_diff_rejects_huge_replay() {
+        let source = Chip8::builder().rng_seed(7).build().unwrap();
+        let mut peer = Chip8::new().unwrap();
+        let mut diff = source.state_diff(&peer);
+        diff.random_draws = u64::MAX;
+        diff.pc = 0x300;
+
+        // Decoding succeeds, but applying would replay too many draws
+        let mut diff = StateDiff::from_bytes(&diff.to_bytes()).unwrap();
+        assert!(matches!(
+            peer.apply_diff(&diff),
+            Err(Chip8Error::InvalidStateDiff(_))
+        ));
+        assert_eq!(peer.pc, 0x200);
+        assert_eq!(peer.random_draws(), 0);
+
+        diff.random_draws = MAX_REPLAYED_DRAWS;
+        peer.apply_diff(&diff).unwrap();
+        assert_eq!(peer.pc, 0x300);
+    }
+}
@@ -0,0 +1,287 @@
+//! Disassembler for CHIP-8 programs.
+//!
+//! This module turns raw opcodes into human-readable mnemonics, following the
+//! notation of Cowgod's Chip-8 Technical Reference (e.g. `LD V1, 0x2A`). It is
+//! intended for debuggers and ROM analysis tools and never executes anything.
+
+use std::ops::Range;
+
+use crate::Chip8;
+use crate::consts::ROM_START_ADDRESS;
+use crate::instruction::{DecodedOp, Instruction, InstructionType};
+
+/// Approximate instruction statistics for a ROM, see [`instruction_count_estimate()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RomEstimate {
+    /// Number of 16-bit words in the ROM (`rom.len() / 2`).
+    pub words: usize,
+    /// Number of words that decode to a known instruction.
+    pub instructions: usize,
+    /// Address ranges of consecutive words that don't decode to any instruction
+    /// and are therefore likely data, such as sprites.
+    pub data_regions: Vec<Range<u16>>,
+}
+
+/// Estimates how many instructions a ROM contains.
+///
+/// CHIP-8 ROMs mix code and data without any marker, so this is a heuristic:
+/// every word that decodes to a known instruction is counted as one, and runs
+/// of undecodable words are reported as data. Sprite bytes that happen to look
+/// like valid opcodes are counted as instructions. This is intended for
+/// progress indicators and quick analysis, not for exact results.
+///
+/// # Arguments
+///
+/// * `rom`: The program's binary data.
+///
+/// # Returns
+///
+/// A [`RomEstimate`] with the counts and the likely data regions, with
+/// addresses relative to where the ROM is loaded (`0x200`).
+pub fn instruction_count_estimate(rom: &[u8]) -> RomEstimate {
+    let mut estimate = RomEstimate {
+        words: rom.len() / 2,
+        instructions: 0,
+        data_regions: Vec::new(),
+    };
+
+    for (address, opcode) in words(rom) {
+        if Instruction::new(opcode).decode().is_ok() {
+            estimate.instructions += 1;
+            continue;
+        }
+        match estimate.data_regions.last_mut() {
+            Some(region) if region.end == address => region.end = address + 2,
+            _ => estimate.data_regions.push(address..address + 2),
+        }
+    }
+    estimate
+}
+
+/// Disassembles a single opcode into its mnemonic.
+///
+/// Opcodes that don't correspond to a known instruction are rendered as a raw
+/// data word, e.g. `DW 0xFFFF`, since ROMs commonly embed sprite data between
+/// instructions.
+///
+/// # Arguments
+///
+/// * `opcode`: The 16-bit opcode to disassemble.
+///
+/// # Examples
+///
+/// ```rust
+/// use chip8_core::disassemble;
+///
+/// assert_eq!(disassemble(0x00E0), "CLS");
+/// assert_eq!(disassemble(0x612A), "LD V1, 0x2A");
+/// ```
+pub fn disassemble(opcode: u16) -> String {
+    match Instruction::new(opcode).decode() {
+        Ok(op) => mnemonic(op),
+        Err(_) => format!("DW {:#06X}", opcode),
+    }
+}
+
+/// Disassembles a whole ROM.
+///
+/// The ROM is read as consecutive big-endian 16-bit words. A trailing odd byte
+/// is ignored.
+///
+/// # Arguments
+///
+/// * `rom`: The program's binary data.
+///
+/// # Returns
+///
+/// A list of `(address, mnemonic)` pairs, with addresses relative to where the
+/// ROM is loaded (`0x200`).
+pub fn disassemble_rom(rom: &[u8]) -> Vec<(u16, String)> {
+    words(rom)
+        .map(|(address, opcode)| (address, disassemble(opcode)))
+        .collect()
+}
+
+/// Disassembles only the instructions of a ROM that belong to a given category.
+///
+/// This is useful to map control flow, e.g. by extracting all jumps and calls
+/// with [`InstructionType::FlowControl`]. Opcodes that can't be decoded are
+/// never included, even if they are classified, like the SUPER-CHIP and
+/// XO-CHIP extensions that aren't executed yet.
+///
+/// # Arguments
+///
+/// * `rom`: The program's binary data.
+/// * `ty`: The category of instructions to keep.
+///
+/// # Returns
+///
+/// A list of `(address, mnemonic)` pairs for the matching instructions.
+pub fn disassemble_rom_filtered(rom: &[u8], ty: InstructionType) -> Vec<(u16, String)> {
+    words(rom)
+        .filter_map(|(address, opcode)| {
+            let instruction = Instruction::new(opcode);
+            if instruction.instruction_type() != ty {
+                return None;
+            }
+            let op = instruction.decode().ok()?;
+            Some((address, mnemonic(op)))
+        })
+        .collect()
+}
+
+impl Chip8 {
+    /// Disassembles the instruction stored in memory at an address.
+    ///
+    /// This is [`disassemble()`] applied to the word at `addr`, e.g. to show
+    /// the next instruction at PC in a debugger.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: The address of the instruction's first byte.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` with the mnemonic.
+    /// * `None` if the word at `addr` extends past the end of memory.
+    pub fn disassemble_at(&self, addr: usize) -> Option<String> {
+        self.memory.read_word(addr).map(disassemble)
+    }
+}
+
+/// Iterates over the 16-bit words of a ROM together with their load address.
+fn words(rom: &[u8]) -> impl Iterator<Item = (u16, u16)> + '_ {
+    rom.chunks_exact(2).enumerate().map(|(index, bytes)| {
+        let address = (ROM_START_ADDRESS + index * 2) as u16;
+        (address, u16::from_be_bytes([bytes[0], bytes[1]]))
+    })
+}
+
+/// Returns the mnemonic for a decoded operation.
+fn mnemonic(op: DecodedOp) -> String {
+    match op {
+        DecodedOp::ClearScreen => "CLS".to_string(),
+        DecodedOp::Return => "RET".to_string(),
+        DecodedOp::Exit => "EXIT".to_string(),
+        DecodedOp::Jump(nnn) => format!("JP {:#05X}", nnn),
+        DecodedOp::Call(nnn) => format!("CALL {:#05X}", nnn),
+        DecodedOp::SkipIfVxEqNn { x, nn } => format!("SE V{:X}, {:#04X}", x, nn),
+        DecodedOp::SkipIfVxNeNn { x, nn } => format!("SNE V{:X}, {:#04X}", x, nn),
+        DecodedOp::SkipIfVxEqVy { x, y } => format!("SE V{:X}, V{:X}", x, y),
+        DecodedOp::SetVxNn { x, nn } => format!("LD V{:X}, {:#04X}", x, nn),
+        DecodedOp::AddVxNn { x, nn } => format!("ADD V{:X}, {:#04X}", x, nn),
+        DecodedOp::SetVxVy { x, y } => format!("LD V{:X}, V{:X}", x, y),
+        DecodedOp::OrVxVy { x, y } => format!("OR V{:X}, V{:X}", x, y),
+        DecodedOp::AndVxVy { x, y } => format!("AND V{:X}, V{:X}", x, y),
+        DecodedOp::XorVxVy { x, y } => format!("XOR V{:X}, V{:X}", x, y),
+        DecodedOp::AddVxVy { x, y } => format!("ADD V{:X}, V{:X}", x, y),
+        DecodedOp::SubVxVy { x, y } => format!("SUB V{:X}, V{:X}", x, y),
+        DecodedOp::ShrVx { x, y } => format!("SHR V{:X}, V{:X}", x, y),
+        DecodedOp::SubnVxVy { x, y } => format!("SUBN V{:X}, V{:X}", x, y),
+        DecodedOp::ShlVx { x, y } => format!("SHL V{:X}, V{:X}", x, y),
+        DecodedOp::SkipIfVxNeVy { x, y } => format!("SNE V{:X}, V{:X}", x, y),
+        DecodedOp::SetI(nnn) => format!("LD I, {:#05X}", nnn),
+        DecodedOp::JumpV0(nnn) => format!("JP V0, {:#05X}", nnn),
+        DecodedOp::Random { x, nn } => format!("RND V{:X}, {:#04X}", x, nn),
+        DecodedOp::Draw { x, y, n } => format!("DRW V{:X}, V{:X}, {}", x, y, n),
+        DecodedOp::SkipIfKey { x } => format!("SKP V{:X}", x),
+        DecodedOp::SkipIfNotKey { x } => format!("SKNP V{:X}", x),
+        DecodedOp::SetVxDt { x } => format!("LD V{:X}, DT", x),
+        DecodedOp::WaitKey { x } => format!("LD V{:X}, K", x),
+        DecodedOp::SetDt { x } => format!("LD DT, V{:X}", x),
+        DecodedOp::SetSt { x } => format!("LD ST, V{:X}", x),
+        DecodedOp::AddIVx { x } => format!("ADD I, V{:X}", x),
+        DecodedOp::SetIFont { x } => format!("LD F, V{:X}", x),
+        DecodedOp::StoreBcd { x } => format!("LD B, V{:X}", x),
+        DecodedOp::StoreRegs { x } => format!("LD [I], V{:X}", x),
+        DecodedOp::LoadRegs { x } => format!("LD V{:X}, [I]", x),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble() {
+        assert_eq!(disassemble(0x00E0), "CLS");
+        assert_eq!(disassemble(0x00EE), "RET");
+        assert_eq!(disassemble(0x1234), "JP 0x234");
+        assert_eq!(disassemble(0x612A), "LD V1, 0x2A");
+        assert_eq!(disassemble(0x8AB4), "ADD VA, VB");
+        assert_eq!(disassemble(0xD125), "DRW V1, V2, 5");
+        assert_eq!(disassemble(0xF365), "LD V3, [I]");
+        assert_eq!(disassemble(0xFFFF), "DW 0xFFFF");
+    }
+
+    #[test]
+    fn test_disassemble_at() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&[0x00, 0xE0, 0xD1, 0x25]).unwrap();
+        assert_eq!(
+            chip8.disassemble_at(0x202).as_deref(),
+            Some("DRW V1, V2, 5")
+        );
+        assert_eq!(chip8.disassemble_at(0x200).as_deref(), Some("CLS"));
+        assert_eq!(chip8.disassemble_at(0xFFE).as_deref(), Some("DW 0x0000"));
+        assert_eq!(chip8.disassemble_at(0xFFF), None);
+    }
+
+    #[test]
+    fn test_disassemble_rom() {
+        let rom = [0x00, 0xE0, 0xA2, 0x2A, 0x12];
+        assert_eq!(
+            disassemble_rom(&rom),
+            vec![
+                (0x200, "CLS".to_string()),
+                (0x202, "LD I, 0x22A".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instruction_count_estimate() {
+        let rom = [
+            0xA2, 0x0A, // LD I, 0x20A
+            0xD0, 0x15, // DRW V0, V1, 5
+            0x12, 0x04, // JP 0x204
+            0xF0, 0xF0, // sprite data
+            0x50, 0x51, // sprite data
+            0x00, 0xEE, // RET
+            0xFF, 0xFF, // data
+            0xFF, // trailing byte
+        ];
+        assert_eq!(
+            instruction_count_estimate(&rom),
+            RomEstimate {
+                words: 7,
+                instructions: 4,
+                data_regions: vec![0x206..0x20A, 0x20C..0x20E],
+            }
+        );
+    }
+
+    #[test]
+    fn test_disassemble_rom_filtered() {
+        let rom = [
+            0x60, 0x05, // LD V0, 0x05
+            0x22, 0x10, // CALL 0x210
+            0xFF, 0xFF, // data
+            0xD0, 0x15, // DRW V0, V1, 5
+            0x12, 0x00, // JP 0x200
+            0x00, 0xEE, // RET
+        ];
+        assert_eq!(
+            disassemble_rom_filtered(&rom, InstructionType::FlowControl),
+            vec![
+                (0x202, "CALL 0x210".to_string()),
+                (0x208, "JP 0x200".to_string()),
+                (0x20A, "RET".to_string()),
+            ]
+        );
+        assert_eq!(
+            disassemble_rom_filtered(&rom, InstructionType::Display),
+            vec![(0x206, "DRW V0, V1, 5".to_string())]
+        );
+    }
+}
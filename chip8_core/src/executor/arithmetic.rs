@@ -172,6 +172,7 @@ impl Chip8 {
     /// # Side Effects
     ///
     /// Sets VF register to 1 if carry occurs, 0 otherwise.
+    /// When Vx is VF itself, the flag is written last and overwrites the result.
     pub(super) fn add_vx_vy(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
         let &vy = self
             .registers
@@ -209,6 +210,7 @@ impl Chip8 {
     /// # Side Effects
     ///
     /// Sets VF register to 1 if no borrow occurs (Vx >= Vy), 0 if borrow occurs.
+    /// When Vx is VF itself, the flag is written last and overwrites the result.
     pub(super) fn sub_vx_vy(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
         let &vy = self
             .registers
@@ -228,27 +230,38 @@ impl Chip8 {
         Ok(())
     }
 
-    /// **8XY6 - SHR Vx**: Shift Vx right by one bit, set VF to the shifted-out bit.
+    /// **8XY6 - SHR Vx {, Vy}**: Shift Vx right by one bit, set VF to the shifted-out bit.
     ///
     /// This instruction shifts the value in register Vx one bit to the right.
     /// The least significant bit (LSB) before the shift is stored in VF.
+    /// If the `shift_uses_vy` quirk is enabled, Vy is first copied into Vx.
     ///
     /// # Arguments
     ///
     /// * `x` - Register index (0-15)
+    /// * `y` - Source register index (0-15), only used with the `shift_uses_vy` quirk
     ///
     /// # Errors
     ///
-    /// Returns `Chip8Error::InvalidRegister` if the register index is out of bounds.
+    /// Returns `Chip8Error::InvalidRegister` if either register index is out of bounds.
     ///
     /// # Side Effects
     ///
     /// Sets VF register to the value of the LSB before the shift operation.
-    pub(super) fn shift_vx_right(&mut self, x: usize) -> Result<(), Chip8Error> {
+    /// When Vx is VF itself, the flag is written last and overwrites the result.
+    pub(super) fn shift_vx_right(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
+        let &vy = self
+            .registers
+            .get(y)
+            .ok_or(Chip8Error::InvalidRegister(y))?;
+        let shift_uses_vy = self.quirks.shift_uses_vy;
         let vx = self
             .registers
             .get_mut(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
+        if shift_uses_vy {
+            *vx = vy;
+        }
         // Store the bit that will be shifted out
         let shifted_out = *vx & 0x1;
         *vx >>= 1;
@@ -277,6 +290,7 @@ impl Chip8 {
     /// # Side Effects
     ///
     /// Sets VF register to 1 if no borrow occurs (Vy >= Vx), 0 if borrow occurs.
+    /// When Vx is VF itself, the flag is written last and overwrites the result.
     pub(super) fn sub_vy_vx(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
         let &vy = self
             .registers
@@ -296,27 +310,38 @@ impl Chip8 {
         Ok(())
     }
 
-    /// **8XYE - SHL Vx**: Shift Vx left by one bit, set VF to the shifted-out bit.
+    /// **8XYE - SHL Vx {, Vy}**: Shift Vx left by one bit, set VF to the shifted-out bit.
     ///
     /// This instruction shifts the value in register Vx one bit to the left.
     /// The most significant bit (MSB) before the shift is stored in VF.
+    /// If the `shift_uses_vy` quirk is enabled, Vy is first copied into Vx.
     ///
     /// # Arguments
     ///
     /// * `x` - Register index (0-15)
+    /// * `y` - Source register index (0-15), only used with the `shift_uses_vy` quirk
     ///
     /// # Errors
     ///
-    /// Returns `Chip8Error::InvalidRegister` if the register index is out of bounds.
+    /// Returns `Chip8Error::InvalidRegister` if either register index is out of bounds.
     ///
     /// # Side Effects
     ///
     /// Sets VF register to the value of the MSB before the shift operation.
-    pub(super) fn shift_vx_left(&mut self, x: usize) -> Result<(), Chip8Error> {
+    /// When Vx is VF itself, the flag is written last and overwrites the result.
+    pub(super) fn shift_vx_left(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
+        let &vy = self
+            .registers
+            .get(y)
+            .ok_or(Chip8Error::InvalidRegister(y))?;
+        let shift_uses_vy = self.quirks.shift_uses_vy;
         let vx = self
             .registers
             .get_mut(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
+        if shift_uses_vy {
+            *vx = vy;
+        }
         // Store the bit that will be shifted out (MSB)
         let shifted_out = (*vx >> 7) & 0x1;
         *vx <<= 1;
@@ -333,6 +358,8 @@ impl Chip8 {
     /// This instruction generates a random 8-bit number, performs a bitwise AND
     /// operation with the immediate value NN, and stores the result in register Vx.
     /// This is commonly used for random number generation with a specific range or mask.
+    /// The number comes from the machine's generator, which can be seeded with
+    /// [`Chip8Builder::rng_seed()`](crate::Chip8Builder::rng_seed) for reproducible runs.
     ///
     /// # Arguments
     ///
@@ -347,7 +374,8 @@ impl Chip8 {
             .registers
             .get_mut(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
-        *vx = rand::rng().random_range(0..=255) & nn;
+        *vx = self.rng.random_range(0..=255) & nn;
+        self.random_draws += 1;
         Ok(())
     }
 }
@@ -482,6 +510,129 @@ mod tests {
         assert_eq!(chip8.registers[0xF], 1, "VF should contain shifted out bit");
     }
 
+    #[test]
+    fn test_op_8fy4_add_flag_overwrites_result() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0xF] = 0xFF;
+        chip8.registers[1] = 0x01;
+        run_instruction(&mut chip8, 0x8F14).unwrap();
+        assert_eq!(chip8.registers[0xF], 1, "Carry flag should win over result");
+
+        chip8.registers[0xF] = 0x10;
+        run_instruction(&mut chip8, 0x8F14).unwrap();
+        assert_eq!(
+            chip8.registers[0xF], 0,
+            "No-carry flag should win over result"
+        );
+    }
+
+    #[test]
+    fn test_op_8fy5_sub_flag_overwrites_result() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0xF] = 0x10;
+        chip8.registers[1] = 0x01;
+        run_instruction(&mut chip8, 0x8F15).unwrap();
+        assert_eq!(
+            chip8.registers[0xF], 1,
+            "No-borrow flag should win over result"
+        );
+
+        chip8.registers[0xF] = 0x01;
+        chip8.registers[1] = 0x10;
+        run_instruction(&mut chip8, 0x8F15).unwrap();
+        assert_eq!(
+            chip8.registers[0xF], 0,
+            "Borrow flag should win over result"
+        );
+    }
+
+    #[test]
+    fn test_op_8fy7_subn_flag_overwrites_result() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0xF] = 0x10;
+        chip8.registers[1] = 0x01;
+        run_instruction(&mut chip8, 0x8F17).unwrap();
+        assert_eq!(
+            chip8.registers[0xF], 0,
+            "Borrow flag should win over result"
+        );
+
+        chip8.registers[0xF] = 0x01;
+        chip8.registers[1] = 0x10;
+        run_instruction(&mut chip8, 0x8F17).unwrap();
+        assert_eq!(
+            chip8.registers[0xF], 1,
+            "No-borrow flag should win over result"
+        );
+    }
+
+    #[test]
+    fn test_op_8fy6_8fye_shift_flag_overwrites_result() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0xF] = 0b0000_0010;
+        run_instruction(&mut chip8, 0x8F16).unwrap();
+        assert_eq!(
+            chip8.registers[0xF], 0,
+            "Shifted-out bit should win over result"
+        );
+
+        chip8.registers[0xF] = 0b1000_0001;
+        run_instruction(&mut chip8, 0x8F1E).unwrap();
+        assert_eq!(
+            chip8.registers[0xF], 1,
+            "Shifted-out bit should win over result"
+        );
+    }
+
+    #[test]
+    fn test_set_quirks_between_instructions() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert_eq!(*chip8.quirks(), Quirks::default());
+
+        chip8.registers[1] = 0b0000_1000;
+        chip8.registers[2] = 0b0100_0000;
+
+        // Modern behavior: V1 is shifted in place
+        run_instruction(&mut chip8, 0x8126).unwrap();
+        assert_eq!(chip8.registers[1], 0b0000_0100);
+
+        // VIP behavior: V2 is copied into V1 before shifting
+        chip8.set_quirks(Quirks {
+            shift_uses_vy: true,
+            ..Quirks::default()
+        });
+        assert!(chip8.quirks().shift_uses_vy);
+        run_instruction(&mut chip8, 0x8126).unwrap();
+        assert_eq!(chip8.registers[1], 0b0010_0000);
+    }
+
+    #[test]
+    fn test_shift_quirk_matrix() {
+        // (opcode, shift_uses_vy, expected V1, expected VF, expected V2)
+        let cases = [
+            (0x8126, false, 0b0110_1000, 1, 0b1000_0110),
+            (0x8126, true, 0b0100_0011, 0, 0b1000_0110),
+            (0x812E, false, 0b1010_0010, 1, 0b1000_0110),
+            (0x812E, true, 0b0000_1100, 1, 0b1000_0110),
+        ];
+
+        for (opcode, shift_uses_vy, v1, vf, v2) in cases {
+            let mut chip8 = Chip8::new().unwrap();
+            chip8.set_quirks(Quirks {
+                shift_uses_vy,
+                ..Quirks::default()
+            });
+            chip8.registers[1] = 0b1101_0001;
+            chip8.registers[2] = 0b1000_0110;
+
+            run_instruction(&mut chip8, opcode).unwrap();
+            let case = format!("{opcode:04X} with shift_uses_vy = {shift_uses_vy}");
+            assert_eq!(chip8.registers[1], v1, "V1 for {case}");
+            assert_eq!(chip8.registers[0xF], vf, "VF for {case}");
+            assert_eq!(chip8.registers[2], v2, "Vy must not change for {case}");
+        }
+    }
+
     #[test]
     fn test_op_cxkk_rnd_vx() {
         let mut chip8 = Chip8::new().unwrap();
@@ -519,4 +670,23 @@ mod tests {
             chip8.reset().unwrap();
         }
     }
+
+    #[test]
+    fn test_random_draws() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert_eq!(chip8.random_draws(), 0);
+
+        for _ in 0..5 {
+            run_instruction(&mut chip8, 0xC1FF).unwrap();
+        }
+        run_instruction(&mut chip8, 0x6105).unwrap();
+        assert_eq!(chip8.random_draws(), 5);
+
+        // Failed draws aren't counted
+        assert!(chip8.set_vx_to_random_and_nn(16, 0xFF).is_err());
+        assert_eq!(chip8.random_draws(), 5);
+
+        chip8.reset().unwrap();
+        assert_eq!(chip8.random_draws(), 0);
+    }
 }
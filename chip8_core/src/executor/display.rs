@@ -2,7 +2,8 @@
 //!
 //! This module contains implementations for all display-related instructions,
 //! including screen clearing and sprite drawing. The CHIP-8 display is a 64x32
-//! monochrome screen where sprites are drawn using XOR operations.
+//! (or 128x64 in high resolution mode) monochrome screen where sprites are
+//! drawn using XOR operations.
 
 use crate::{Chip8, Chip8Error};
 
@@ -14,6 +15,10 @@ impl Chip8 {
     /// Each byte represents a row of 8 pixels. Sprites are drawn using XOR,
     /// so if a sprite pixel overlaps with an existing pixel, both pixels are turned off.
     ///
+    /// The sprite is always read from memory, including the interpreter area:
+    /// pointing I at a built-in font character with `FX29` and drawing with
+    /// N = 5 renders that hexadecimal digit.
+    ///
     /// # Arguments
     ///
     /// * `x` - Register index containing X coordinate (0-15)
@@ -30,8 +35,14 @@ impl Chip8 {
     ///
     /// - Modifies pixels in the framebuffer using XOR operation
     /// - Sets VF register to 1 if any pixel collision occurs, 0 otherwise
+    /// - Records the collision for [`Chip8::last_draw_collided()`] and
+    ///   [`Chip8::collision_count()`]
     /// - Sets display_updated flag to true to indicate screen refresh needed
-    /// - Coordinates wrap around screen boundaries (X: 0-63, Y: 0-31)
+    /// - Invokes the collision hook, if one is registered, when a collision occurs
+    /// - Coordinates wrap around screen boundaries (X: 0-63, Y: 0-31, or
+    ///   X: 0-127, Y: 0-63 in high resolution mode)
+    /// - Pixels past the right or bottom edge are clipped, or wrap around if
+    ///   the `draw_offscreen_clips` quirk is disabled
     pub(super) fn draw_sprite(&mut self, x: usize, y: usize, n: u8) -> Result<(), Chip8Error> {
         let &vx = self
             .registers
@@ -42,48 +53,154 @@ impl Chip8 {
             .get(y)
             .ok_or(Chip8Error::InvalidRegister(y))?;
 
-        let x_coord = (vx % 64) as usize;
-        let y_coord = (vy % 32) as usize;
+        let (width, screen_height) = (self.width(), self.height());
+        let x_coord = vx as usize % width;
+        let y_coord = vy as usize % screen_height;
         let height = n as usize;
 
-        let vf = self
-            .registers
-            .last_mut()
-            .ok_or(Chip8Error::InvalidRegister(0xf))?;
-        *vf = 0;
+        let mut collision = false;
 
         for row in 0..height {
-            let y_pos = y_coord + row;
-            if y_pos >= 32 {
+            let Some(y_pos) = self.sprite_coord(y_coord + row, screen_height) else {
                 break;
-            }
+            };
 
             let sprite_byte = self
                 .memory
                 .read_byte(self.i as usize + row)
-                .ok_or(Chip8Error::IndexError(self.i + row as u16))?;
+                .ok_or(Chip8Error::IndexError(self.i.wrapping_add(row as u16)))?;
 
-            for col in 0..8 {
-                let x_pos = x_coord + col;
-                if x_pos >= 64 {
-                    continue;
-                }
+            collision |= self.draw_sprite_row(x_coord, y_pos, sprite_byte)?;
+        }
+
+        let vf = self
+            .registers
+            .last_mut()
+            .ok_or(Chip8Error::InvalidRegister(0xf))?;
+        *vf = collision as u8;
+        self.last_draw_collided = collision;
+        self.collision_count += u64::from(collision);
+        if collision && let Some(hook) = self.collision_hook.as_mut() {
+            hook();
+        }
 
-                if (sprite_byte & (0x80 >> col)) != 0 {
-                    let pixel_index = y_pos * 64 + x_pos;
-                    let pixel = self
-                        .framebuffer
-                        .get_mut(pixel_index)
-                        .ok_or(Chip8Error::FrameBufferOverflow(pixel_index))?;
-                    if *pixel == 1 {
-                        *vf = 1; // Collision
-                    }
-                    *pixel ^= 1;
+        self.display_updated = true;
+        Ok(())
+    }
+
+    /// Maps a sprite pixel coordinate onto the display along one axis.
+    ///
+    /// # Returns
+    ///
+    /// The coordinate, wrapped to the opposite edge if it is off screen and
+    /// the `draw_offscreen_clips` quirk is disabled, or `None` if it is
+    /// clipped.
+    fn sprite_coord(&self, pos: usize, size: usize) -> Option<usize> {
+        if pos < size {
+            Some(pos)
+        } else if self.quirks.draw_offscreen_clips {
+            None
+        } else {
+            Some(pos % size)
+        }
+    }
+
+    /// XORs one 8-pixel sprite row onto the framebuffer, clipping or wrapping at the right edge.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if a pixel that was on got turned off.
+    fn draw_sprite_row(&mut self, x: usize, y: usize, byte: u8) -> Result<bool, Chip8Error> {
+        let width = self.width();
+        let mut collision = false;
+
+        for col in 0..8 {
+            let Some(x_pos) = self.sprite_coord(x + col, width) else {
+                continue;
+            };
+
+            if (byte & (0x80 >> col)) != 0 {
+                let pixel_index = y * width + x_pos;
+                let pixel = self
+                    .framebuffer
+                    .get_mut(pixel_index)
+                    .ok_or(Chip8Error::FrameBufferOverflow(pixel_index))?;
+                if *pixel == 1 {
+                    collision = true;
                 }
+                *pixel ^= 1;
             }
         }
+        Ok(collision)
+    }
+
+    /// Draws a sprite as if `DXYN` had been executed, without assembling an opcode.
+    ///
+    /// This exposes `DXYN` to test harnesses so they can
+    /// draw directly instead of writing an instruction to memory and running it.
+    /// The sprite is read from memory at I, exactly as for the real instruction.
+    /// Only available in tests or with the `test-util` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `x_reg` - Register index containing X coordinate (0-15)
+    /// * `y_reg` - Register index containing Y coordinate (0-15)
+    /// * `height` - Height of the sprite in bytes (1-15)
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as the `DXYN` instruction.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn draw_sprite_at(
+        &mut self,
+        x_reg: usize,
+        y_reg: usize,
+        height: u8,
+    ) -> Result<(), Chip8Error> {
+        self.draw_sprite(x_reg, y_reg, height)
+    }
+
+    /// Draws sprite bytes directly at pixel coordinates.
+    ///
+    /// Unlike `DXYN`, the sprite isn't read from memory at I, so drawing can be
+    /// tested without setting up memory or registers. Coordinates wrap and the
+    /// sprite is clipped like for `DXYN`, and the display is marked as updated,
+    /// but VF is left untouched and the collision hook is not invoked. Only
+    /// available in tests or with the `test-util` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate of the top-left corner
+    /// * `y` - Y coordinate of the top-left corner
+    /// * `sprite` - The sprite rows, one byte per 8-pixel row
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if any pixel was turned off (a collision), `Ok(false)` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::FrameBufferOverflow` if framebuffer access is out of bounds.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn draw_raw_sprite(
+        &mut self,
+        x: usize,
+        y: usize,
+        sprite: &[u8],
+    ) -> Result<bool, Chip8Error> {
+        let x_coord = x % self.width();
+        let y_coord = y % self.height();
+
+        let mut collision = false;
+        for (row, &byte) in sprite.iter().enumerate() {
+            let Some(y_pos) = self.sprite_coord(y_coord + row, self.height()) else {
+                break;
+            };
+            collision |= self.draw_sprite_row(x_coord, y_pos, byte)?;
+        }
+
         self.display_updated = true;
-        Ok(())
+        Ok(collision)
     }
 }
 
@@ -238,6 +355,37 @@ mod tests {
         assert_eq!(chip8.framebuffer[8 * 64 + 13], 1); // Last bit (6+7)
     }
 
+    #[test]
+    fn test_collision_hook() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut chip8 = Chip8::new().unwrap();
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = fired.clone();
+        chip8.set_collision_hook(Box::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        chip8.i = 0x300;
+        chip8
+            .memory
+            .write_at(&[0xFF], 0x300)
+            .expect("Failed to write memory");
+        chip8.registers[1] = 10;
+        chip8.registers[2] = 5;
+
+        // Clean draw should not fire the hook
+        run_instruction(&mut chip8, 0xD121).unwrap();
+        assert_eq!(chip8.registers[0xF], 0);
+        assert_eq!(fired.load(Ordering::SeqCst), 0);
+
+        // Overlapping draw should fire the hook exactly once
+        run_instruction(&mut chip8, 0xD121).unwrap();
+        assert_eq!(chip8.registers[0xF], 1);
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn test_sprite_xor_behavior() {
         let mut chip8 = Chip8::new().unwrap();
@@ -269,4 +417,121 @@ mod tests {
         // Should have collision detection
         assert_eq!(chip8.registers[0xF], 1);
     }
+
+    #[test]
+    fn test_draw_raw_sprite() {
+        let mut chip8 = Chip8::new().unwrap();
+        let sprite = [0b1100_0000, 0b0011_0000];
+
+        assert!(!chip8.draw_raw_sprite(10, 5, &sprite).unwrap());
+        let framebuffer = chip8.framebuffer();
+        assert_eq!(&framebuffer[5 * 64 + 10..5 * 64 + 14], &[1, 1, 0, 0]);
+        assert_eq!(&framebuffer[6 * 64 + 10..6 * 64 + 14], &[0, 0, 1, 1]);
+        assert!(chip8.is_display_updated());
+        assert_eq!(chip8.registers[0xF], 0);
+
+        // Drawing the same sprite again erases it and reports the collision
+        assert!(chip8.draw_raw_sprite(10, 5, &sprite).unwrap());
+        assert!(chip8.framebuffer().iter().all(|&p| p == 0));
+        assert_eq!(chip8.registers[0xF], 0);
+    }
+
+    #[test]
+    fn test_draw_font_digit() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0] = 0x0; // Digit
+        chip8.registers[1] = 2; // X
+        chip8.registers[2] = 1; // Y
+
+        run_instruction(&mut chip8, 0xF029).unwrap();
+        run_instruction(&mut chip8, 0xD125).unwrap();
+
+        let expected: [[u8; 4]; 5] = [
+            [1, 1, 1, 1],
+            [1, 0, 0, 1],
+            [1, 0, 0, 1],
+            [1, 0, 0, 1],
+            [1, 1, 1, 1],
+        ];
+        for (row, pixels) in expected.iter().enumerate() {
+            let start = (1 + row) * 64 + 2;
+            assert_eq!(&chip8.framebuffer()[start..start + 4], pixels, "row {row}");
+            // The low nibble of font bytes is blank
+            assert_eq!(&chip8.framebuffer()[start + 4..start + 8], &[0, 0, 0, 0]);
+        }
+        assert_eq!(chip8.registers[0xF], 0);
+    }
+
+    #[test]
+    fn test_last_draw_collided() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0xFF], 0x300).unwrap();
+        assert!(!chip8.last_draw_collided());
+
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        assert!(!chip8.last_draw_collided());
+
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        assert!(chip8.last_draw_collided());
+
+        // Overwriting VF doesn't affect the flag
+        run_instruction(&mut chip8, 0x6F00).unwrap();
+        assert!(chip8.last_draw_collided());
+
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        assert!(!chip8.last_draw_collided());
+    }
+
+    #[test]
+    fn test_collision_count() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0xFF], 0x300).unwrap();
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        assert_eq!(chip8.collision_count(), 0);
+
+        // Erasing the sprite collides, drawing it on the blank screen doesn't
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        assert_eq!(chip8.collision_count(), 1);
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        assert_eq!(chip8.collision_count(), 2);
+
+        chip8.reset().unwrap();
+        assert_eq!(chip8.collision_count(), 0);
+    }
+
+    #[test]
+    fn test_draw_offscreen_quirk() {
+        for draw_offscreen_clips in [true, false] {
+            let mut chip8 = Chip8::new().unwrap();
+            chip8.set_quirks(Quirks {
+                draw_offscreen_clips,
+                ..Quirks::default()
+            });
+            chip8.registers[0] = 62; // X
+            chip8.registers[1] = 31; // Y
+            chip8.i = 0x300;
+            chip8.memory.write_at(&[0xFF, 0xFF], 0x300).unwrap();
+            run_instruction(&mut chip8, 0xD012).unwrap();
+
+            let lit = |x: usize, y: usize| chip8.framebuffer()[y * 64 + x];
+            assert_eq!((lit(62, 31), lit(63, 31)), (1, 1));
+            // The 6 pixels past the right edge and the row below the bottom
+            let wrapped = u8::from(!draw_offscreen_clips);
+            for x in 0..6 {
+                assert_eq!(
+                    lit(x, 31),
+                    wrapped,
+                    "x = {x}, clips = {draw_offscreen_clips}"
+                );
+            }
+            assert_eq!(lit(6, 31), 0);
+            assert_eq!(lit(62, 0), wrapped);
+            assert_eq!(lit(0, 0), wrapped);
+            let count = chip8.framebuffer().iter().filter(|&&p| p == 1).count();
+            assert_eq!(count, if draw_offscreen_clips { 2 } else { 16 });
+        }
+    }
 }
@@ -4,6 +4,7 @@
 //! including jumps, subroutine calls, and conditional skip operations. These instructions
 //! are fundamental to program execution and control structure in CHIP-8 programs.
 
+use crate::consts::LONG_INSTRUCTION_OPCODE;
 use crate::{Chip8, Chip8Error};
 
 impl Chip8 {
@@ -21,7 +22,7 @@ impl Chip8 {
     /// - Clears all pixels in the framebuffer
     /// - Sets the display_updated flag to true
     pub(super) fn clear_screen(&mut self) -> Result<(), Chip8Error> {
-        self.framebuffer.iter_mut().for_each(|p| *p = 0);
+        self.framebuffer.fill(0);
         self.display_updated = true;
 
         Ok(())
@@ -47,6 +48,24 @@ impl Chip8 {
         Ok(())
     }
 
+    /// **00FD - EXIT**: Halt the interpreter (SUPER-CHIP).
+    ///
+    /// This instruction stops program execution. Once halted, further calls to
+    /// `run` do nothing until the machine is reset.
+    ///
+    /// # Errors
+    ///
+    /// This instruction should not fail under normal circumstances.
+    ///
+    /// # Side Effects
+    ///
+    /// Sets the halted flag.
+    pub(super) fn exit(&mut self) -> Result<(), Chip8Error> {
+        self.halted = true;
+
+        Ok(())
+    }
+
     /// **1NNN - JP addr**: Jump to address NNN.
     ///
     /// This instruction sets the program counter to the address NNN, causing
@@ -96,6 +115,26 @@ impl Chip8 {
         Ok(())
     }
 
+    /// Skips the instruction at the program counter.
+    ///
+    /// This is the shared primitive behind the conditional skip instructions.
+    /// The program counter normally advances by 2, but by 4 if the next
+    /// instruction is the XO-CHIP `F000 NNNN` long instruction, so that the
+    /// skip never lands on its 16-bit address operand.
+    ///
+    /// # Side Effects
+    ///
+    /// Advances the program counter by 2 or 4, wrapping around on overflow.
+    pub(super) fn skip_next_instruction(&mut self) {
+        let next = self.memory.read_word(self.pc as usize);
+        let len = if next == Some(LONG_INSTRUCTION_OPCODE) {
+            4
+        } else {
+            2
+        };
+        self.pc = self.pc.wrapping_add(len);
+    }
+
     /// **3XNN - SE Vx, byte**: Skip next instruction if Vx equals NN.
     ///
     /// This instruction compares the value in register Vx with the immediate value NN.
@@ -112,14 +151,14 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// May increment the program counter by 2 if the condition is true.
+    /// May skip the next instruction if the condition is true.
     pub(super) fn skip_if_vx_equals_nn(&mut self, x: usize, nn: u8) -> Result<(), Chip8Error> {
         let &vx = self
             .registers
             .get(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
         if vx == nn {
-            self.pc = self.pc.wrapping_add(2);
+            self.skip_next_instruction();
         }
 
         Ok(())
@@ -141,14 +180,14 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// May increment the program counter by 2 if the condition is true.
+    /// May skip the next instruction if the condition is true.
     pub(super) fn skip_if_vx_not_equals_nn(&mut self, x: usize, nn: u8) -> Result<(), Chip8Error> {
         let &vx = self
             .registers
             .get(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
         if vx != nn {
-            self.pc = self.pc.wrapping_add(2);
+            self.skip_next_instruction();
         }
 
         Ok(())
@@ -170,7 +209,7 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// May increment the program counter by 2 if the condition is true.
+    /// May skip the next instruction if the condition is true.
     pub(super) fn skip_if_vx_equals_vy(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
         let &vx = self
             .registers
@@ -181,7 +220,7 @@ impl Chip8 {
             .get(y)
             .ok_or(Chip8Error::InvalidRegister(y))?;
         if vx == vy {
-            self.pc = self.pc.wrapping_add(2);
+            self.skip_next_instruction();
         }
 
         Ok(())
@@ -203,7 +242,7 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// May increment the program counter by 2 if the condition is true.
+    /// May skip the next instruction if the condition is true.
     pub(super) fn skip_if_vx_not_equals_vy(
         &mut self,
         x: usize,
@@ -218,7 +257,7 @@ impl Chip8 {
             .get(y)
             .ok_or(Chip8Error::InvalidRegister(y))?;
         if vx != vy {
-            self.pc = self.pc.wrapping_add(2);
+            self.skip_next_instruction();
         }
 
         Ok(())
@@ -385,4 +424,37 @@ mod tests {
         assert_eq!(chip8.pc, initial_pc + 2);
         assert_eq!(chip8.sp, 0);
     }
+
+    #[test]
+    fn test_op_00fd_exit() {
+        let mut chip8 = Chip8::new().unwrap();
+        run_instruction(&mut chip8, 0x00FD).unwrap();
+        assert!(chip8.is_halted());
+        assert_eq!(chip8.pc, 0x202);
+
+        // Halted machines don't execute further instructions
+        chip8.run().unwrap();
+        assert_eq!(chip8.pc, 0x202);
+    }
+
+    #[test]
+    fn test_skip_next_instruction() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0] = 0x12;
+        // SE V0, 0x12 followed by F000 1234, then LD V1, 0x56
+        chip8
+            .memory
+            .write_at(&[0x30, 0x12, 0xF0, 0x00, 0x12, 0x34, 0x61, 0x56], 0x200)
+            .unwrap();
+        chip8.run().unwrap();
+        assert_eq!(chip8.pc, 0x206);
+        chip8.run().unwrap();
+        assert_eq!(chip8.registers[1], 0x56);
+
+        // Regular instructions are skipped by 2 bytes
+        chip8.pc = 0x300;
+        chip8.memory.write_at(&[0x61, 0x00], 0x300).unwrap();
+        chip8.skip_next_instruction();
+        assert_eq!(chip8.pc, 0x302);
+    }
 }
@@ -24,7 +24,7 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// May increment the program counter by 2 if the specified key is pressed.
+    /// May skip the next instruction if the specified key is pressed.
     pub(super) fn skip_if_key_pressed(&mut self, x: usize) -> Result<(), Chip8Error> {
         let &vx = self
             .registers
@@ -35,7 +35,7 @@ impl Chip8 {
             .get(vx as usize)
             .ok_or(Chip8Error::InvalidKey(vx))?;
         if key != 0 {
-            self.pc = self.pc.wrapping_add(2);
+            self.skip_next_instruction();
         }
 
         Ok(())
@@ -58,7 +58,7 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// May increment the program counter by 2 if the specified key is not pressed.
+    /// May skip the next instruction if the specified key is not pressed.
     pub(super) fn skip_if_key_not_pressed(&mut self, x: usize) -> Result<(), Chip8Error> {
         let &vx = self
             .registers
@@ -69,7 +69,7 @@ impl Chip8 {
             .get(vx as usize)
             .ok_or(Chip8Error::InvalidKey(vx))?;
         if key == 0 {
-            self.pc = self.pc.wrapping_add(2);
+            self.skip_next_instruction();
         }
 
         Ok(())
@@ -94,6 +94,7 @@ impl Chip8 {
     ///
     /// - Stores the pressed key value (0-15) in register Vx when a key is pressed
     /// - Decrements the program counter by 2 if no key is pressed (causing the instruction to repeat)
+    /// - Records the target register while the machine is blocked waiting for a key press
     ///
     /// # Behavior
     ///
@@ -119,8 +120,33 @@ impl Chip8 {
             // No key pressed - repeat this instruction by moving PC back
             self.pc = self.pc.wrapping_sub(2);
         }
+        self.waiting_for_key = (!key_pressed).then_some(x);
         Ok(())
     }
+
+    /// Presses a key, then executes an opcode as if it were at the program counter.
+    ///
+    /// This is a shortcut for input tests: the opcode is written to memory at
+    /// PC and run like any other instruction, so PC advances normally. The key
+    /// stays pressed afterwards. Only available in tests or with the
+    /// `test-util` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The index of the key to press (0-15)
+    /// * `opcode` - The instruction to execute
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::MemoryError` if the opcode can't be written at PC,
+    /// or any error raised while executing the instruction.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn press_and_run(&mut self, key: u8, opcode: u16) -> Result<(), Chip8Error> {
+        self.key_press(key);
+        self.memory
+            .write_at(&opcode.to_be_bytes(), self.pc as usize)?;
+        self.run()
+    }
 }
 
 #[cfg(test)]
@@ -285,4 +311,31 @@ mod tests {
         let result = run_instruction(&mut chip8, 0xE19E);
         assert!(matches!(result, Err(Chip8Error::InvalidKey(16))));
     }
+
+    #[test]
+    fn test_press_and_run_ex9e() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[1] = 7;
+
+        chip8.press_and_run(7, 0xE19E).unwrap();
+        assert_eq!(chip8.pc, 0x204);
+        assert_eq!(chip8.keyboard[7], 1);
+
+        chip8.press_and_run(3, 0xE19E).unwrap();
+        assert_eq!(chip8.pc, 0x208);
+    }
+
+    #[test]
+    fn test_pending_key_wait() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert_eq!(chip8.pending_key_wait(), None);
+
+        run_instruction(&mut chip8, 0xF30A).unwrap();
+        assert_eq!(chip8.pending_key_wait(), Some(3));
+
+        chip8.key_press(0x7);
+        chip8.run().unwrap();
+        assert_eq!(chip8.pending_key_wait(), None);
+        assert_eq!(chip8.registers[3], 0x7);
+    }
 }
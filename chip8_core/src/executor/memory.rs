@@ -96,22 +96,29 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// Sets the sound timer to the value in register Vx.
+    /// Sets the sound timer to the value in register Vx, recording a sound edge
+    /// if the beeper starts or stops.
     pub(super) fn set_sound_timer_to_vx(&mut self, x: usize) -> Result<(), Chip8Error> {
         let &vx = self
             .registers
             .get(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
+        let was_beeping = self.should_beep();
         self.st = vx;
+        self.update_sound_edge(was_beeping);
         Ok(())
     }
 
     /// **FX1E - ADD I, Vx**: Add register Vx to index register I.
     ///
     /// This instruction adds the value in register Vx to the index register I.
-    /// The addition wraps around on overflow. This is commonly used to advance
+    /// The addition wraps around on 16-bit overflow. This is commonly used to advance
     /// the index register when processing arrays or sprite data.
     ///
+    /// I itself is not checked against the RAM size: instructions that access
+    /// memory through I fail with `Chip8Error::IndexError` instead of wrapping
+    /// into unrelated memory.
+    ///
     /// # Arguments
     ///
     /// * `x` - Register index (0-15) containing the value to add to I
@@ -160,7 +167,8 @@ impl Chip8 {
             .get(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
         // Each font character is 5 bytes, font starts at FONT_START_ADDRESS
-        self.i = crate::memory::FONT_START_ADDRESS as u16 + (vx as u16 * 5);
+        self.i = crate::memory::FONT_START_ADDRESS as u16
+            + (vx as u16 * crate::memory::FONT_SPRITE_SIZE as u16);
         Ok(())
     }
 
@@ -183,7 +191,8 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// Modifies 3 bytes of memory starting at address I with the BCD representation.
+    /// Modifies 3 bytes of memory starting at address I with the BCD representation,
+    /// reporting each byte to the memory write hook, if one is registered.
     ///
     /// # Examples
     ///
@@ -197,8 +206,7 @@ impl Chip8 {
             .get(x)
             .ok_or(Chip8Error::InvalidRegister(x))?;
         let slice: [u8; 3] = [vx / 100, (vx % 100) / 10, vx % 10];
-        self.memory.write_at(&slice, self.i as usize)?;
-        Ok(())
+        self.write_at_index(&slice)
     }
 
     /// **FX55 - LD \[I\], Vx**: Store registers V0 through Vx in memory starting at location I.
@@ -218,7 +226,8 @@ impl Chip8 {
     ///
     /// # Side Effects
     ///
-    /// Copies (x+1) register values into consecutive memory locations starting at I.
+    /// Copies (x+1) register values into consecutive memory locations starting at I,
+    /// reporting each byte to the memory write hook, if one is registered.
     ///
     /// # Examples
     ///
@@ -232,8 +241,7 @@ impl Chip8 {
             .filter_map(|(i, v)| if i <= x { Some(*v) } else { None })
             .collect::<Vec<u8>>();
 
-        self.memory.write_at(&buf, self.i as usize)?;
-        Ok(())
+        self.write_at_index(&buf)
     }
 
     /// **FX65 - LD Vx, \[I\]**: Load registers V0 through Vx from memory starting at location I.
@@ -260,6 +268,7 @@ impl Chip8 {
     /// If x=3, this instruction loads memory locations I, I+1, I+2, and I+3
     /// into registers V0, V1, V2, and V3 respectively.
     pub(super) fn load_registers_from_memory(&mut self, x: usize) -> Result<(), Chip8Error> {
+        self.check_index_range(x + 1)?;
         let memory = self
             .memory
             .get(self.i as usize..=self.i as usize + x)
@@ -273,6 +282,39 @@ impl Chip8 {
         }
         Ok(())
     }
+
+    /// Writes `bytes` to memory starting at I and reports each byte written
+    /// to the memory write hook.
+    ///
+    /// All instructions that write to memory go through this function.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::IndexError` if the range extends past the end of RAM.
+    /// Returns `Chip8Error::MemoryError` if the write fails.
+    fn write_at_index(&mut self, bytes: &[u8]) -> Result<(), Chip8Error> {
+        self.check_index_range(bytes.len())?;
+        let start = self.i as usize;
+        self.memory.write_at(bytes, start)?;
+        if let Some(hook) = self.memory_write_hook.as_mut() {
+            for (offset, &byte) in bytes.iter().enumerate() {
+                hook(start + offset, byte);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `len` bytes starting at I lie within RAM.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::IndexError` if the range extends past the end of RAM.
+    fn check_index_range(&self, len: usize) -> Result<(), Chip8Error> {
+        if self.i as usize + len > self.memory.size() {
+            return Err(Chip8Error::IndexError(self.i));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -472,4 +514,75 @@ mod tests {
         run_instruction(&mut chip8, 0xF11E).unwrap();
         assert_eq!(chip8.i, 0); // Should wrap to 0
     }
+
+    #[test]
+    fn test_index_access_past_end_of_ram() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.i = 0xFF0;
+        chip8.registers[1] = 0x20;
+        run_instruction(&mut chip8, 0xF11E).unwrap();
+        assert_eq!(chip8.i, 0x1010); // I is not bound to the RAM size
+
+        assert!(matches!(
+            run_instruction(&mut chip8, 0xF355),
+            Err(Chip8Error::IndexError(0x1010))
+        ));
+        assert!(matches!(
+            run_instruction(&mut chip8, 0xF365),
+            Err(Chip8Error::IndexError(0x1010))
+        ));
+        assert!(matches!(
+            run_instruction(&mut chip8, 0xF133),
+            Err(Chip8Error::IndexError(0x1010))
+        ));
+
+        // A range straddling the end of RAM fails without a partial write
+        chip8.i = 0xFFE;
+        chip8.registers[0] = 0xAA;
+        assert!(matches!(
+            run_instruction(&mut chip8, 0xF355),
+            Err(Chip8Error::IndexError(0xFFE))
+        ));
+        assert_eq!(chip8.memory.read_byte(0xFFE), Some(0));
+        assert_eq!(chip8.memory.read_byte(0xFFF), Some(0));
+    }
+
+    #[test]
+    fn test_op_fx33_past_end_of_ram() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0] = 123;
+        chip8.i = 4094;
+        assert!(matches!(
+            run_instruction(&mut chip8, 0xF033),
+            Err(Chip8Error::IndexError(4094))
+        ));
+        assert_eq!(chip8.memory.read_byte(4094), Some(0));
+        assert_eq!(chip8.memory.read_byte(4095), Some(0));
+
+        // The last 3 bytes of RAM still fit
+        chip8.i = 4093;
+        run_instruction(&mut chip8, 0xF033).unwrap();
+        assert_eq!(chip8.memory.read_byte(4095), Some(3));
+    }
+
+    #[test]
+    fn test_memory_write_hook() {
+        use std::sync::{Arc, Mutex};
+
+        let mut chip8 = Chip8::new().unwrap();
+        let writes = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&writes);
+        chip8.set_memory_write_hook(Box::new(move |address, value| {
+            log.lock().unwrap().push((address, value));
+        }));
+
+        chip8.i = 0x300;
+        chip8.registers[5] = 123;
+        run_instruction(&mut chip8, 0xF533).unwrap();
+
+        assert_eq!(
+            *writes.lock().unwrap(),
+            vec![(0x300, 1), (0x301, 2), (0x302, 3)]
+        );
+    }
 }
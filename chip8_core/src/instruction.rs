@@ -1,3 +1,5 @@
+use crate::Chip8Error;
+
 /// Categories of CHIP-8 instructions based on their functionality.
 ///
 /// This enum provides a high-level classification of instruction types,
@@ -5,7 +7,7 @@
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum InstructionType {
     /// Flow control instructions that change program execution flow.
-    /// Includes: 0x00EE (ret), 0x1NNN (jmp), 0x2NNN (call), 0xBNNN (jmp+v0)
+    /// Includes: 0x00EE (ret), 0x00FD (exit), 0x1NNN (jmp), 0x2NNN (call), 0xBNNN (jmp+v0)
     FlowControl,
 
     /// Conditional skip instructions that may skip the next instruction.
@@ -24,6 +26,20 @@ pub enum InstructionType {
     /// Includes: 0x00E0 (cls), 0xDXYN (draw)
     Display,
 
+    /// SUPER-CHIP and XO-CHIP display extensions, classified but not executed yet.
+    /// Includes: 0x00CN, 0x00DN, 0x00FB, 0x00FC (scroll), 0x00FE, 0x00FF
+    /// (resolution), 0xFX30 (big font), 0xFN01 (select planes)
+    SuperChipDisplay,
+
+    /// SUPER-CHIP and XO-CHIP memory extensions, classified but not executed yet.
+    /// Includes: 0x5XY2, 0x5XY3 (save/load register range), 0xF000 (long
+    /// I = NNNN), 0xFX75, 0xFX85 (save/load flags)
+    ExtendedMemory,
+
+    /// XO-CHIP audio instructions, classified but not executed yet.
+    /// Includes: 0xF002 (load pattern), 0xFX3A (pitch)
+    Audio,
+
     /// Input/output instructions for keyboard and user interaction.
     /// Includes: 0xFX0A (wait key)
     InputOutput,
@@ -35,6 +51,89 @@ pub enum InstructionType {
     /// Random number generation instructions.
     /// Includes: 0xCXNN
     Random,
+
+    /// Opcodes that don't correspond to any known instruction, including
+    /// 0x0NNN machine code calls.
+    Unknown,
+}
+
+/// A CHIP-8 operation with its operands, produced by [`Instruction::decode()`].
+///
+/// Each variant corresponds to exactly one opcode pattern, so the executor and
+/// tools like the disassembler can match on it exhaustively instead of
+/// re-matching raw nibbles. Register operands are indices (0-15) into V0-VF.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DecodedOp {
+    /// `00E0` - Clear the display.
+    ClearScreen,
+    /// `00EE` - Return from a subroutine.
+    Return,
+    /// `00FD` - Halt the interpreter (SUPER-CHIP).
+    Exit,
+    /// `1NNN` - Jump to address NNN.
+    Jump(u16),
+    /// `2NNN` - Call subroutine at NNN.
+    Call(u16),
+    /// `3XNN` - Skip the next instruction if Vx == NN.
+    SkipIfVxEqNn { x: usize, nn: u8 },
+    /// `4XNN` - Skip the next instruction if Vx != NN.
+    SkipIfVxNeNn { x: usize, nn: u8 },
+    /// `5XY0` - Skip the next instruction if Vx == Vy.
+    SkipIfVxEqVy { x: usize, y: usize },
+    /// `6XNN` - Set Vx = NN.
+    SetVxNn { x: usize, nn: u8 },
+    /// `7XNN` - Set Vx = Vx + NN.
+    AddVxNn { x: usize, nn: u8 },
+    /// `8XY0` - Set Vx = Vy.
+    SetVxVy { x: usize, y: usize },
+    /// `8XY1` - Set Vx = Vx OR Vy.
+    OrVxVy { x: usize, y: usize },
+    /// `8XY2` - Set Vx = Vx AND Vy.
+    AndVxVy { x: usize, y: usize },
+    /// `8XY3` - Set Vx = Vx XOR Vy.
+    XorVxVy { x: usize, y: usize },
+    /// `8XY4` - Set Vx = Vx + Vy, VF = carry.
+    AddVxVy { x: usize, y: usize },
+    /// `8XY5` - Set Vx = Vx - Vy, VF = NOT borrow.
+    SubVxVy { x: usize, y: usize },
+    /// `8XY6` - Shift Vx right by one, VF = shifted out bit.
+    ShrVx { x: usize, y: usize },
+    /// `8XY7` - Set Vx = Vy - Vx, VF = NOT borrow.
+    SubnVxVy { x: usize, y: usize },
+    /// `8XYE` - Shift Vx left by one, VF = shifted out bit.
+    ShlVx { x: usize, y: usize },
+    /// `9XY0` - Skip the next instruction if Vx != Vy.
+    SkipIfVxNeVy { x: usize, y: usize },
+    /// `ANNN` - Set I = NNN.
+    SetI(u16),
+    /// `BNNN` - Jump to address NNN + V0.
+    JumpV0(u16),
+    /// `CXNN` - Set Vx = random byte AND NN.
+    Random { x: usize, nn: u8 },
+    /// `DXYN` - Draw an N-byte sprite at (Vx, Vy).
+    Draw { x: usize, y: usize, n: u8 },
+    /// `EX9E` - Skip the next instruction if the key in Vx is pressed.
+    SkipIfKey { x: usize },
+    /// `EXA1` - Skip the next instruction if the key in Vx is not pressed.
+    SkipIfNotKey { x: usize },
+    /// `FX07` - Set Vx = delay timer.
+    SetVxDt { x: usize },
+    /// `FX0A` - Wait for a key press and store it in Vx.
+    WaitKey { x: usize },
+    /// `FX15` - Set delay timer = Vx.
+    SetDt { x: usize },
+    /// `FX18` - Set sound timer = Vx.
+    SetSt { x: usize },
+    /// `FX1E` - Set I = I + Vx.
+    AddIVx { x: usize },
+    /// `FX29` - Set I to the font sprite for digit Vx.
+    SetIFont { x: usize },
+    /// `FX33` - Store the BCD representation of Vx at I, I+1 and I+2.
+    StoreBcd { x: usize },
+    /// `FX55` - Store V0 through Vx in memory starting at I.
+    StoreRegs { x: usize },
+    /// `FX65` - Load V0 through Vx from memory starting at I.
+    LoadRegs { x: usize },
 }
 
 /// Decoded representation of a single 16-bit CHIP-8 instruction.
@@ -124,6 +223,73 @@ impl Instruction {
         self.nnn
     }
 
+    /// Returns `true` if the opcode is a recognized instruction.
+    ///
+    /// This is a shortcut for checking that [`Instruction::decode()`]
+    /// succeeds, e.g. for tools that mark data words in a ROM.
+    pub fn is_valid(&self) -> bool {
+        self.decode().is_ok()
+    }
+
+    /// Decodes the instruction into the operation it performs.
+    ///
+    /// This is the single place where opcode patterns are matched; the
+    /// executor only matches on the resulting [`DecodedOp`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DecodedOp)` with the operation and its operands.
+    /// * `Err(Chip8Error::InvalidOpCode)` if the opcode doesn't correspond to
+    ///   any known instruction.
+    pub fn decode(&self) -> Result<DecodedOp, Chip8Error> {
+        let (x, y, n, nn, nnn) = (self.x, self.y, self.n, self.nn, self.nnn);
+
+        let op = match (self.instr, x, y, n) {
+            (0, 0, 0xE, 0) => DecodedOp::ClearScreen,
+            (0, 0, 0xE, 0xE) => DecodedOp::Return,
+            (0, 0, 0xF, 0xD) => DecodedOp::Exit,
+            (1, _, _, _) => DecodedOp::Jump(nnn),
+            (2, _, _, _) => DecodedOp::Call(nnn),
+            (3, _, _, _) => DecodedOp::SkipIfVxEqNn { x, nn },
+            (4, _, _, _) => DecodedOp::SkipIfVxNeNn { x, nn },
+            (5, _, _, 0) => DecodedOp::SkipIfVxEqVy { x, y },
+            (6, _, _, _) => DecodedOp::SetVxNn { x, nn },
+            (7, _, _, _) => DecodedOp::AddVxNn { x, nn },
+            (8, _, _, 0) => DecodedOp::SetVxVy { x, y },
+            (8, _, _, 1) => DecodedOp::OrVxVy { x, y },
+            (8, _, _, 2) => DecodedOp::AndVxVy { x, y },
+            (8, _, _, 3) => DecodedOp::XorVxVy { x, y },
+            (8, _, _, 4) => DecodedOp::AddVxVy { x, y },
+            (8, _, _, 5) => DecodedOp::SubVxVy { x, y },
+            (8, _, _, 6) => DecodedOp::ShrVx { x, y },
+            (8, _, _, 7) => DecodedOp::SubnVxVy { x, y },
+            (8, _, _, 0xE) => DecodedOp::ShlVx { x, y },
+            (9, _, _, 0) => DecodedOp::SkipIfVxNeVy { x, y },
+            (0xA, _, _, _) => DecodedOp::SetI(nnn),
+            (0xB, _, _, _) => DecodedOp::JumpV0(nnn),
+            (0xC, _, _, _) => DecodedOp::Random { x, nn },
+            (0xD, _, _, _) => DecodedOp::Draw { x, y, n },
+            (0xE, _, 0x9, 0xE) => DecodedOp::SkipIfKey { x },
+            (0xE, _, 0xA, 0x1) => DecodedOp::SkipIfNotKey { x },
+            (0xF, _, 0x0, 0x7) => DecodedOp::SetVxDt { x },
+            (0xF, _, 0x0, 0xA) => DecodedOp::WaitKey { x },
+            (0xF, _, 0x1, 0x5) => DecodedOp::SetDt { x },
+            (0xF, _, 0x1, 0x8) => DecodedOp::SetSt { x },
+            (0xF, _, 0x1, 0xE) => DecodedOp::AddIVx { x },
+            (0xF, _, 0x2, 0x9) => DecodedOp::SetIFont { x },
+            (0xF, _, 0x3, 0x3) => DecodedOp::StoreBcd { x },
+            (0xF, _, 0x5, 0x5) => DecodedOp::StoreRegs { x },
+            (0xF, _, 0x6, 0x5) => DecodedOp::LoadRegs { x },
+            _ => {
+                return Err(Chip8Error::InvalidOpCode(format!(
+                    "Unknown opcode: {}",
+                    self
+                )));
+            }
+        };
+        Ok(op)
+    }
+
     /// Returns the instruction type classification for this instruction.
     ///
     /// This method analyzes the opcode pattern and returns the appropriate
@@ -134,11 +300,19 @@ impl Instruction {
     /// An `InstructionType` enum indicating the category of this instruction.
     pub fn instruction_type(&self) -> InstructionType {
         match (self.instr, self.x, self.y, self.n) {
-            // Flow control instructions
+            // 0x00XX instructions, matched on the full low byte
+            (0, 0, 0xE, 0) => InstructionType::Display, // Clear screen
             (0, 0, 0xE, 0xE) => InstructionType::FlowControl, // Return from subroutine
-            (1, _, _, _) => InstructionType::FlowControl,     // Jump to address
-            (2, _, _, _) => InstructionType::FlowControl,     // Call subroutine
-            (0xB, _, _, _) => InstructionType::FlowControl,   // Jump to V0 + NNN
+            (0, 0, 0xC | 0xD, _) => InstructionType::SuperChipDisplay, // Scroll down/up N lines
+            (0, 0, 0xF, 0xB | 0xC) => InstructionType::SuperChipDisplay, // Scroll right/left
+            (0, 0, 0xF, 0xD) => InstructionType::FlowControl, // Exit interpreter
+            (0, 0, 0xF, 0xE | 0xF) => InstructionType::SuperChipDisplay, // Low/high resolution
+            (0, _, _, _) => InstructionType::Unknown,   // Machine code routines
+
+            // Flow control instructions
+            (1, _, _, _) => InstructionType::FlowControl, // Jump to address
+            (2, _, _, _) => InstructionType::FlowControl, // Call subroutine
+            (0xB, _, _, _) => InstructionType::FlowControl, // Jump to V0 + NNN
 
             // Conditional skip instructions
             (3, _, _, _) => InstructionType::ConditionalSkip, // Skip if Vx == NN
@@ -162,12 +336,20 @@ impl Instruction {
             (0xF, _, 0x6, 0x5) => InstructionType::MemoryOp, // Load registers from memory
 
             // Display instructions
-            (0, 0, 0xE, 0) => InstructionType::Display, // Clear screen
             (0xD, _, _, _) => InstructionType::Display, // Draw sprite
 
             // Input/output instructions
             (0xF, _, 0x0, 0xA) => InstructionType::InputOutput, // Wait for key press
 
+            // SUPER-CHIP and XO-CHIP extensions
+            (0xF, _, 0x3, 0x0) => InstructionType::SuperChipDisplay, // Set I to big font location
+            (0xF, _, 0x0, 0x1) => InstructionType::SuperChipDisplay, // Select drawing planes
+            (5, _, _, 0x2 | 0x3) => InstructionType::ExtendedMemory, // Save/load Vx..Vy
+            (0xF, 0, 0x0, 0x0) => InstructionType::ExtendedMemory,   // Set I to the next word
+            (0xF, _, 0x7 | 0x8, 0x5) => InstructionType::ExtendedMemory, // Save/load flags
+            (0xF, 0, 0x0, 0x2) => InstructionType::Audio,            // Load audio pattern
+            (0xF, _, 0x3, 0xA) => InstructionType::Audio,            // Set pitch to Vx
+
             // Timer instructions
             (0xF, _, 0x0, 0x7) => InstructionType::Timer, // Set Vx to delay timer
             (0xF, _, 0x1, 0x5) => InstructionType::Timer, // Set delay timer to Vx
@@ -176,8 +358,7 @@ impl Instruction {
             // Random number generation
             (0xC, _, _, _) => InstructionType::Random, // Set Vx to random & NN
 
-            // Default case - this should not happen for valid instructions
-            _ => InstructionType::FlowControl, // Default fallback
+            _ => InstructionType::Unknown,
         }
     }
 }
@@ -200,3 +381,119 @@ impl std::fmt::Display for Instruction {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let decode = |opcode| Instruction::new(opcode).decode().unwrap();
+
+        assert_eq!(decode(0x00E0), DecodedOp::ClearScreen);
+        assert_eq!(decode(0x00EE), DecodedOp::Return);
+        assert_eq!(decode(0x00FD), DecodedOp::Exit);
+        assert_eq!(decode(0x1ABC), DecodedOp::Jump(0xABC));
+        assert_eq!(decode(0x2123), DecodedOp::Call(0x123));
+        assert_eq!(decode(0x6A2B), DecodedOp::SetVxNn { x: 0xA, nn: 0x2B });
+        assert_eq!(decode(0x8126), DecodedOp::ShrVx { x: 1, y: 2 });
+        assert_eq!(decode(0x812E), DecodedOp::ShlVx { x: 1, y: 2 });
+        assert_eq!(decode(0xB300), DecodedOp::JumpV0(0x300));
+        assert_eq!(decode(0xD125), DecodedOp::Draw { x: 1, y: 2, n: 5 });
+        assert_eq!(decode(0xE3A1), DecodedOp::SkipIfNotKey { x: 3 });
+        assert_eq!(decode(0xF40A), DecodedOp::WaitKey { x: 4 });
+        assert_eq!(decode(0xF565), DecodedOp::LoadRegs { x: 5 });
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        for opcode in [0x0123, 0x5121, 0x8128, 0x9121, 0xE1FF, 0xF1FF] {
+            assert!(matches!(
+                Instruction::new(opcode).decode(),
+                Err(Chip8Error::InvalidOpCode(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_is_valid() {
+        for opcode in [
+            0x00E0, 0x00EE, 0x00FD, 0x1234, 0x8126, 0xD125, 0xE19E, 0xF165,
+        ] {
+            assert!(Instruction::new(opcode).is_valid(), "{opcode:04X}");
+        }
+        for opcode in [0x0000, 0x0123, 0x5121, 0x8128, 0xE1FF, 0xFFFF] {
+            assert!(!Instruction::new(opcode).is_valid(), "{opcode:04X}");
+        }
+    }
+
+    #[test]
+    fn test_instruction_type_00xx() {
+        for low in 0x00..=0xFF_u16 {
+            let expected = match low {
+                0xE0 => InstructionType::Display,
+                0xEE | 0xFD => InstructionType::FlowControl,
+                0xC0..=0xDF | 0xFB | 0xFC | 0xFE | 0xFF => InstructionType::SuperChipDisplay,
+                _ => InstructionType::Unknown,
+            };
+            assert_eq!(
+                Instruction::new(low).instruction_type(),
+                expected,
+                "{low:04X}"
+            );
+        }
+        assert_eq!(
+            Instruction::new(0x0123).instruction_type(),
+            InstructionType::Unknown
+        );
+    }
+
+    #[test]
+    fn test_instruction_type_unknown() {
+        for opcode in [0x5121, 0x9121, 0xE1FF, 0xF1FF] {
+            assert_eq!(
+                Instruction::new(opcode).instruction_type(),
+                InstructionType::Unknown,
+                "{opcode:04X}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_instruction_type_extensions() {
+        let cases = [
+            (0x00C4, InstructionType::SuperChipDisplay),
+            (0x00D4, InstructionType::SuperChipDisplay),
+            (0x00FF, InstructionType::SuperChipDisplay),
+            (0xF330, InstructionType::SuperChipDisplay),
+            (0xF201, InstructionType::SuperChipDisplay),
+            (0x5122, InstructionType::ExtendedMemory),
+            (0x5123, InstructionType::ExtendedMemory),
+            (0xF000, InstructionType::ExtendedMemory),
+            (0xF375, InstructionType::ExtendedMemory),
+            (0xF385, InstructionType::ExtendedMemory),
+            (0xF002, InstructionType::Audio),
+            (0xF43A, InstructionType::Audio),
+        ];
+        for (opcode, expected) in cases {
+            let instruction = Instruction::new(opcode);
+            assert_eq!(instruction.instruction_type(), expected, "{opcode:04X}");
+            // Classified, but not supported by the decoder
+            assert!(!instruction.is_valid(), "{opcode:04X}");
+        }
+
+        // Neighboring opcodes keep their classic classification
+        assert_eq!(
+            Instruction::new(0x5120).instruction_type(),
+            InstructionType::ConditionalSkip
+        );
+        assert_eq!(
+            Instruction::new(0xF333).instruction_type(),
+            InstructionType::MemoryOp
+        );
+        assert_eq!(
+            Instruction::new(0xF100).instruction_type(),
+            InstructionType::Unknown
+        );
+    }
+}
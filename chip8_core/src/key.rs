@@ -0,0 +1,90 @@
+//! Type-safe keys of the CHIP-8 hexadecimal keypad.
+//!
+//! The keypad has 16 keys labelled 0-F. Most of the API takes the key index
+//! as a raw `u8` and ignores values outside that range; [`Key`] makes invalid
+//! indices unrepresentable.
+
+use crate::Chip8Error;
+
+/// A key of the CHIP-8 hexadecimal keypad.
+///
+/// The discriminant of each variant is its key index, so `Key::KeyA as u8`
+/// is `0xA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Key {
+    Key0 = 0x0,
+    Key1 = 0x1,
+    Key2 = 0x2,
+    Key3 = 0x3,
+    Key4 = 0x4,
+    Key5 = 0x5,
+    Key6 = 0x6,
+    Key7 = 0x7,
+    Key8 = 0x8,
+    Key9 = 0x9,
+    KeyA = 0xA,
+    KeyB = 0xB,
+    KeyC = 0xC,
+    KeyD = 0xD,
+    KeyE = 0xE,
+    KeyF = 0xF,
+}
+
+impl Key {
+    /// All keys, ordered by key index.
+    pub const ALL: [Key; 16] = [
+        Key::Key0,
+        Key::Key1,
+        Key::Key2,
+        Key::Key3,
+        Key::Key4,
+        Key::Key5,
+        Key::Key6,
+        Key::Key7,
+        Key::Key8,
+        Key::Key9,
+        Key::KeyA,
+        Key::KeyB,
+        Key::KeyC,
+        Key::KeyD,
+        Key::KeyE,
+        Key::KeyF,
+    ];
+}
+
+impl TryFrom<u8> for Key {
+    type Error = Chip8Error;
+
+    /// Converts a key index (0-15) to a key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::InvalidKey` if the index is greater than 15.
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        Key::ALL
+            .get(index as usize)
+            .copied()
+            .ok_or(Chip8Error::InvalidKey(index))
+    }
+}
+
+impl From<Key> for u8 {
+    fn from(key: Key) -> Self {
+        key as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_u8() {
+        for index in 0..16 {
+            assert_eq!(u8::from(Key::try_from(index).unwrap()), index);
+        }
+        assert_eq!(Key::try_from(0xA).unwrap(), Key::KeyA);
+        assert!(matches!(Key::try_from(16), Err(Chip8Error::InvalidKey(16))));
+    }
+}
@@ -73,15 +73,35 @@
 //!     // chip8.key_release(key_index); // When key is released
 //! }
 //! ```
+mod builder;
 mod consts;
+mod diff;
+mod disassembler;
 mod executor;
 mod instruction;
+mod key;
 mod memory;
+mod quirks;
+mod state;
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 
 use consts::*;
-use instruction::Instruction;
 
-use crate::memory::{Memory, MemoryError};
+pub use crate::builder::Chip8Builder;
+pub use crate::diff::StateDiff;
+pub use crate::disassembler::{
+    RomEstimate, disassemble, disassemble_rom, disassemble_rom_filtered, instruction_count_estimate,
+};
+pub use crate::instruction::{DecodedOp, Instruction, InstructionType};
+pub use crate::key::Key;
+pub use crate::memory::{Memory, MemoryError};
+pub use crate::quirks::Quirks;
+pub use crate::state::Chip8State;
 
 /// Represents the CHIP-8 virtual machine.
 ///
@@ -118,13 +138,97 @@ pub struct Chip8 {
     st: u8,
 
     /// Frame Buffer of the Chip8
-    framebuffer: [u8; FRAMEBUFFER_WIDTH * FRAMEBUFFER_HEIGHT],
+    ///
+    /// Sized for the high resolution mode; only the first `width * height`
+    /// pixels are in use for the current resolution.
+    framebuffer: [u8; HIRES_FRAMEBUFFER_WIDTH * HIRES_FRAMEBUFFER_HEIGHT],
+
+    /// Whether the display is in the 128x64 high resolution mode
+    hires: bool,
 
     /// Keyboard State of the Chip8
     keyboard: [u8; 16],
 
+    /// Keyboard state at the last frame boundary (`tick_timers`)
+    previous_keyboard: [u8; 16],
+
     /// Flag to indicate that the display has been updated
     display_updated: bool,
+
+    /// Target register of an `FX0A` blocked waiting for a key press
+    waiting_for_key: Option<usize>,
+
+    /// Flag to indicate that the program halted with `00FD`
+    halted: bool,
+
+    /// Flag to indicate that the most recent `DXYN` collided
+    last_draw_collided: bool,
+
+    /// Number of `DXYN` draws that collided since the last reset
+    collision_count: u64,
+
+    /// Beeper change not yet reported by `sound_edge()`
+    sound_edge: Option<SoundEdge>,
+
+    /// Byte used to fill RAM and registers on initialization
+    fill_pattern: u8,
+
+    /// Whether the machine boots in the high resolution mode
+    initial_hires: bool,
+
+    /// Whether the font set is write-protected
+    protect_font: bool,
+
+    /// Custom font set loaded instead of the built-in one
+    font: Option<[u8; 80]>,
+
+    /// Whether fetches from odd addresses are recorded
+    warn_on_odd_pc: bool,
+
+    /// Distinct odd addresses instructions were fetched from since the last reset
+    odd_pc_warnings: Vec<u16>,
+
+    /// Seed for the random number generator, or `None` to seed from the OS
+    rng_seed: Option<u64>,
+
+    /// Random number generator used by `CXNN`
+    rng: StdRng,
+
+    /// Number of random bytes drawn by `CXNN` since the last reset
+    random_draws: u64,
+
+    /// Size of the loaded ROM, 0 if none was loaded since the last reset
+    rom_len: usize,
+
+    /// Behavior toggles for instructions that differ between implementations
+    quirks: Quirks,
+
+    /// States recorded before each instruction, used by `step_back`. The
+    /// generator is kept alongside, as it is not part of `Chip8State`.
+    history: VecDeque<(Chip8State, StdRng)>,
+
+    /// Maximum number of states kept in the history (0 disables it)
+    history_depth: usize,
+
+    /// Addresses of the most recently fetched instructions, oldest first.
+    /// Holds up to twice `pc_history_depth` entries so that old entries can
+    /// be dropped in batches; only the last `pc_history_depth` are exposed.
+    pc_history: Vec<u16>,
+
+    /// Maximum number of addresses reported by `pc_history` (0 disables it)
+    pc_history_depth: usize,
+
+    /// Optional callback invoked whenever a sprite draw results in a collision
+    collision_hook: Option<Box<dyn FnMut() + Send>>,
+
+    /// Optional callback invoked for every byte an instruction writes to memory
+    memory_write_hook: Option<Box<dyn FnMut(usize, u8) + Send>>,
+
+    /// Number of instructions executed since the last reset
+    cycle_count: u64,
+
+    /// Pending key events as `(cycle, key, pressed)`, sorted by cycle
+    key_events: VecDeque<(u64, u8, bool)>,
 }
 
 /// Defines the possible errors that can occur during CHIP-8 emulation.
@@ -157,6 +261,97 @@ pub enum Chip8Error {
     /// An instruction referenced an invalid keyboard key (valid range: 0-15).
     #[error("Invalid keyboard key index: {0}")]
     InvalidKey(u8),
+    /// The ROM doesn't fit in memory from the program start address `0x200`.
+    #[error("ROM is too large: {size} bytes, but only {capacity} bytes are available")]
+    RomTooLarge { size: usize, capacity: usize },
+    /// The requested ROM range lies outside the provided data.
+    #[error("ROM range {offset}+{len} is outside the {size}-byte input")]
+    InvalidRomSlice {
+        offset: usize,
+        len: usize,
+        size: usize,
+    },
+    /// Bytes passed to [`StateDiff::from_bytes()`] don't encode a valid diff.
+    #[error("Invalid state diff: {0}")]
+    InvalidStateDiff(&'static str),
+}
+
+impl Chip8Error {
+    /// Returns a stable process exit code for this error.
+    ///
+    /// CLI frontends can exit with this code so that scripts can tell
+    /// failures apart. Code 1 is left for generic frontend errors.
+    ///
+    /// | Variant               | Code |
+    /// |-----------------------|------|
+    /// | `InvalidOpCode`       | 2    |
+    /// | `MemoryError`         | 3    |
+    /// | `PCError`             | 4    |
+    /// | `SPError`             | 5    |
+    /// | `SPOverflow`          | 6    |
+    /// | `FrameBufferOverflow` | 7    |
+    /// | `IndexError`          | 8    |
+    /// | `InvalidRegister`     | 9    |
+    /// | `InvalidKey`          | 10   |
+    /// | `RomTooLarge`         | 11   |
+    /// | `InvalidRomSlice`     | 12   |
+    /// | `InvalidStateDiff`    | 13   |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Chip8Error::InvalidOpCode(_) => 2,
+            Chip8Error::MemoryError(_) => 3,
+            Chip8Error::PCError(_) => 4,
+            Chip8Error::SPError(_) => 5,
+            Chip8Error::SPOverflow(_) => 6,
+            Chip8Error::FrameBufferOverflow(_) => 7,
+            Chip8Error::IndexError(_) => 8,
+            Chip8Error::InvalidRegister(_) => 9,
+            Chip8Error::InvalidKey(_) => 10,
+            Chip8Error::RomTooLarge { .. } => 11,
+            Chip8Error::InvalidRomSlice { .. } => 12,
+            Chip8Error::InvalidStateDiff(_) => 13,
+        }
+    }
+
+    /// Returns `true` if execution can reasonably continue after this error.
+    ///
+    /// An error is recoverable if it is confined to the instruction that
+    /// raised it: the instruction changed nothing and the program counter
+    /// has already moved past it, so a frontend can skip it and run on. This
+    /// is the case for unknown opcodes and out-of-range key values.
+    ///
+    /// Memory, stack, program counter and index register errors mean the
+    /// program has lost track of its own state, and ROM loading errors don't
+    /// come from execution at all, so these are not recoverable.
+    pub fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            Chip8Error::InvalidOpCode(_) | Chip8Error::InvalidKey(_)
+        )
+    }
+}
+
+/// A change of the beeper state, reported by [`Chip8::sound_edge()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEdge {
+    /// The sound timer went from zero to non-zero: the beep starts.
+    Start,
+    /// The sound timer reached zero: the beep stops.
+    Stop,
+}
+
+/// The state of the machine after a call to [`Chip8::step()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    /// The machine can keep executing instructions.
+    Running,
+    /// The program is blocked on `FX0A` until a key is pressed.
+    WaitingForKey,
+    /// The program halted with `00FD`.
+    Halted,
+    /// A `DXYN` ran with the `display_wait` quirk enabled (see [`Quirks`]),
+    /// so the rest of the 60 Hz frame is idle.
+    WaitingForVblank,
 }
 
 impl Chip8 {
@@ -172,8 +367,26 @@ impl Chip8 {
     /// * `Ok(Chip8)` with a new, ready-to-use `Chip8` instance.
     /// * `Err(Chip8Error::LoadFontSetError)` if the font set cannot be loaded, which is an unlikely internal error.
     pub fn new() -> Result<Self, Chip8Error> {
-        Ok(Self {
-            memory: Memory::try_new()?,
+        Ok(Self::with_memory(Memory::try_new()?))
+    }
+
+    /// Creates a new CHIP-8 virtual machine without returning a `Result`.
+    ///
+    /// This is [`Chip8::new()`] for callers that don't want to handle an
+    /// error that can't happen in practice: the font set and its address are
+    /// compile-time constants and always fit in memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the font set can't be loaded, which would be an internal bug.
+    pub fn new_infallible() -> Self {
+        Self::new().expect("the built-in font set always fits in memory")
+    }
+
+    /// Creates a machine in its power-on state around already initialized memory.
+    fn with_memory(memory: Memory) -> Self {
+        Self {
+            memory,
             registers: [0; 16],
             pc: 0x200,
             sp: 0,
@@ -181,10 +394,80 @@ impl Chip8 {
             stack: [0; 16],
             dt: 0,
             st: 0,
-            framebuffer: [0; 64 * 32],
+            framebuffer: [0; HIRES_FRAMEBUFFER_WIDTH * HIRES_FRAMEBUFFER_HEIGHT],
+            hires: false,
             keyboard: [0; 16],
+            previous_keyboard: [0; 16],
             display_updated: false,
-        })
+            waiting_for_key: None,
+            halted: false,
+            last_draw_collided: false,
+            collision_count: 0,
+            sound_edge: None,
+            fill_pattern: 0,
+            initial_hires: false,
+            protect_font: false,
+            font: None,
+            warn_on_odd_pc: false,
+            odd_pc_warnings: Vec::new(),
+            rng_seed: None,
+            rng: StdRng::from_os_rng(),
+            random_draws: 0,
+            rom_len: 0,
+            quirks: Quirks::default(),
+            history: VecDeque::new(),
+            history_depth: 0,
+            pc_history: Vec::new(),
+            pc_history_depth: 0,
+            collision_hook: None,
+            memory_write_hook: None,
+            cycle_count: 0,
+            key_events: VecDeque::new(),
+        }
+    }
+
+    /// Creates `count` independent CHIP-8 virtual machines.
+    ///
+    /// This is a convenience for frontends that run many ROMs at once, such as
+    /// a gallery view. Construction is cheap: the font set is a compile-time
+    /// constant copied into each machine's fixed-size RAM, with no per-instance
+    /// heap allocation.
+    ///
+    /// # Arguments
+    ///
+    /// * `count`: The number of machines to create.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Chip8>)` with `count` ready-to-use machines.
+    /// * `Err(Chip8Error)` if any machine fails to initialize.
+    pub fn new_many(count: usize) -> Result<Vec<Self>, Chip8Error> {
+        (0..count).map(|_| Self::new()).collect()
+    }
+
+    /// Creates a new CHIP-8 virtual machine with a program already loaded.
+    ///
+    /// This is a shortcut for [`Chip8::new()`] followed by [`Chip8::load_rom()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rom`: A byte slice representing the program's binary data.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Chip8)` with a machine ready to run the program from `0x200`.
+    /// * `Err(Chip8Error::RomTooLarge)` if the ROM doesn't fit in memory.
+    pub fn with_rom(rom: &[u8]) -> Result<Self, Chip8Error> {
+        let mut chip8 = Self::new()?;
+        chip8.load_rom(rom)?;
+        Ok(chip8)
+    }
+
+    /// Returns a builder for a machine with a non-default configuration.
+    ///
+    /// See [`Chip8Builder`] for the available options.
+    pub fn builder() -> Chip8Builder {
+        Chip8Builder::new()
     }
 
     /// Resets the CHIP-8 virtual machine to its initial state.
@@ -192,23 +475,51 @@ impl Chip8 {
     /// This is equivalent to turning the machine off and on again. It clears all registers,
     /// memory (except for the font set), the stack, and I/O devices. The program counter
     /// is reset to `0x200`. The font set is reloaded into its standard memory location.
+    /// Memory and registers are refilled with the configured fill pattern, if any.
+    ///
+    /// Only runtime state is cleared. Configuration is preserved: the quirks, the
+    /// builder options (fill pattern, initial resolution, RNG seed, custom
+    /// font, font protection), the collision hook and the history depths.
     ///
     /// # Returns
     ///
     /// * `Ok(())` on successful reset.
     /// * `Err(Chip8Error::LoadFontSetError)` if reloading the font fails, which is an unlikely internal error.
     pub fn reset(&mut self) -> Result<(), Chip8Error> {
-        self.memory = Memory::try_new()?;
-        self.registers = [0; 16];
+        self.memory = Memory::try_new_filled(self.fill_pattern)?;
+        if let Some(font) = &self.font {
+            self.memory.set_font(font);
+        }
+        if self.protect_font {
+            self.memory.protect_font();
+        }
+        self.registers = [self.fill_pattern; 16];
         self.pc = 0x200;
         self.sp = 0;
         self.i = 0;
         self.stack = [0; 16];
         self.dt = 0;
         self.st = 0;
-        self.framebuffer = [0; 64 * 32];
+        self.framebuffer = [0; HIRES_FRAMEBUFFER_WIDTH * HIRES_FRAMEBUFFER_HEIGHT];
+        self.hires = self.initial_hires;
+        if let Some(seed) = self.rng_seed {
+            self.rng = StdRng::seed_from_u64(seed);
+        }
+        self.random_draws = 0;
+        self.rom_len = 0;
+        self.odd_pc_warnings.clear();
         self.keyboard = [0; 16];
+        self.previous_keyboard = [0; 16];
         self.display_updated = false;
+        self.waiting_for_key = None;
+        self.halted = false;
+        self.last_draw_collided = false;
+        self.collision_count = 0;
+        self.sound_edge = None;
+        self.history.clear();
+        self.pc_history.clear();
+        self.cycle_count = 0;
+        self.key_events.clear();
 
         Ok(())
     }
@@ -224,216 +535,1304 @@ impl Chip8 {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the ROM was successfully loaded.
-    /// * `Err(Chip8Error::LoadRomError)` if the ROM is too large to fit in the memory
+    /// * `Ok(usize)` with the number of bytes written if the ROM was successfully loaded.
+    /// * `Err(Chip8Error::RomTooLarge)` if the ROM is too large to fit in the memory
     ///   from the starting address `0x200` to the end of memory.
-    pub fn load_rom(&mut self, rom: &[u8]) -> Result<(), Chip8Error> {
+    pub fn load_rom(&mut self, rom: &[u8]) -> Result<usize, Chip8Error> {
+        let capacity = self.remaining_rom_space();
+        if rom.len() > capacity {
+            return Err(Chip8Error::RomTooLarge {
+                size: rom.len(),
+                capacity,
+            });
+        }
         self.memory.write_at(rom, ROM_START_ADDRESS)?;
-        Ok(())
+        self.rom_len = rom.len();
+        Ok(rom.len())
     }
 
-    /// Returns a read-only slice of the framebuffer.
+    /// Returns the number of bytes available for a ROM.
     ///
-    /// The framebuffer represents the CHIP-8's 64x32 monochrome display.
-    /// Each byte in the slice corresponds to a pixel, with `1` representing
-    /// a pixel that is on and `0` for a pixel that is off. The data is
-    /// stored in row-major order.
-    pub fn framebuffer(&self) -> &[u8] {
-        &self.framebuffer
+    /// This is the room from the program start address `0x200` to the end of
+    /// memory, 3584 bytes with the standard 4 KiB of RAM. A ROM of at most
+    /// this size can be loaded with [`Chip8::load_rom()`].
+    pub fn remaining_rom_space(&self) -> usize {
+        self.memory.remaining_capacity_from(ROM_START_ADDRESS)
     }
 
-    /// Checks if the display has been updated since the last check.
+    /// Returns the checksum of the memory holding the loaded ROM.
     ///
-    /// This flag is set to `true` by instructions that modify the framebuffer,
-    /// such as `00E0` (clear screen) and `DXYN` (draw sprite). The UI layer
-    /// should check this flag each frame to determine if it needs to redraw
-    /// the screen.
-    pub fn is_display_updated(&self) -> bool {
-        self.display_updated
+    /// See [`Memory::checksum_range()`]. Take this right after loading the
+    /// ROM and pass it to [`Chip8::program_changed_since()`] later to detect
+    /// self-modifying code.
+    pub fn program_checksum(&self) -> u32 {
+        self.memory
+            .checksum_range(ROM_START_ADDRESS..ROM_START_ADDRESS + self.rom_len)
     }
 
-    /// Clears the display updated flag.
+    /// Returns `true` if the program region differs from a checksum taken earlier.
     ///
-    /// This should be called by the UI layer after it has redrawn the screen
-    /// based on the `is_display_updated` flag.
-    pub fn clear_display_updated_flag(&mut self) {
-        self.display_updated = false;
-    }
-
-    /// Simulates a key press on the CHIP-8 keypad.
+    /// The program region is the memory the last [`Chip8::load_rom()`] wrote
+    /// to. Writes elsewhere, e.g. to scratch memory past the ROM, aren't
+    /// detected.
     ///
     /// # Arguments
     ///
-    /// * `key_index`: The index of the key to press (0-15). Any value outside
-    ///   this range will be ignored.
-    pub fn key_press(&mut self, key_index: u8) {
-        if let Some(key) = self.keyboard.get_mut(key_index as usize) {
-            *key = 1;
-        }
+    /// * `baseline`: A checksum returned by [`Chip8::program_checksum()`].
+    pub fn program_changed_since(&self, baseline: u32) -> bool {
+        self.program_checksum() != baseline
     }
 
-    /// Simulates a key release on the CHIP-8 keypad.
+    /// Loads a ROM embedded in a larger buffer into memory.
+    ///
+    /// This is [`Chip8::load_rom()`] for `data[offset..offset + len]`, for
+    /// containers that wrap the program with a header or other metadata.
     ///
     /// # Arguments
     ///
-    /// * `key_index`: The index of the key to release (0-15). Any value outside
-    ///   this range will be ignored.
-    pub fn key_release(&mut self, key_index: u8) {
-        if let Some(key) = self.keyboard.get_mut(key_index as usize) {
-            *key = 0;
-        }
+    /// * `data`: The buffer containing the ROM.
+    /// * `offset`: The position of the first ROM byte in `data`.
+    /// * `len`: The size of the ROM in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::InvalidRomSlice` if the range extends past the end
+    /// of `data`, and the errors of [`Chip8::load_rom()`] otherwise.
+    pub fn load_rom_slice(
+        &mut self,
+        data: &[u8],
+        offset: usize,
+        len: usize,
+    ) -> Result<(), Chip8Error> {
+        let rom = offset
+            .checked_add(len)
+            .and_then(|end| data.get(offset..end))
+            .ok_or(Chip8Error::InvalidRomSlice {
+                offset,
+                len,
+                size: data.len(),
+            })?;
+        self.load_rom(rom)?;
+        Ok(())
     }
 
-    /// Decrements both delay and sound timers by 1 if they are greater than 0.
+    /// Returns the font sprite bytes for a hexadecimal digit.
     ///
-    /// This function should be called at exactly 60Hz frequency to maintain proper
-    /// timing behavior that CHIP-8 programs expect. The CHIP-8 specification
-    /// defines that both timers decrement at this rate until they reach zero.
+    /// The bytes are read from the font region of memory, so they reflect
+    /// what `FX29` followed by `DXYN` would actually draw.
     ///
-    /// # Timer Behavior
+    /// # Arguments
     ///
-    /// - **Delay Timer (DT)**: Used by programs for timing delays and synchronization
-    /// - **Sound Timer (ST)**: Controls the duration of the beep sound
+    /// * `digit`: The hexadecimal digit (0x0-0xF).
     ///
-    /// # Usage
+    /// # Returns
     ///
-    /// This function should typically be called in your main emulation loop at
-    /// a consistent 60Hz interval (approximately every 16.67ms).
+    /// * `Some(&[u8])` containing the 5 sprite bytes for the digit.
+    /// * `None` if `digit` is greater than 0xF.
+    pub fn sprite_font_bytes(&self, digit: u8) -> Option<&[u8]> {
+        if digit > 0xF {
+            return None;
+        }
+        let start = memory::FONT_START_ADDRESS + digit as usize * memory::FONT_SPRITE_SIZE;
+        self.memory.get(start..start + memory::FONT_SPRITE_SIZE)
+    }
+
+    /// Checks the machine state for internal consistency.
     ///
-    /// # Note
+    /// This is intended for long-running tests (such as fuzzing) that want to
+    /// catch state corruption early. It verifies that:
+    /// - the stack pointer is within the stack (`sp <= 16`),
+    /// - the program counter points into memory,
+    /// - the index register points into memory,
+    /// - every key state is either `0` or `1`.
     ///
-    /// This function does not handle timing automatically. It is the caller's
-    /// responsibility to ensure it is called at the correct frequency for
-    /// accurate CHIP-8 timing behavior.
-    pub fn tick_timers(&mut self) {
-        if self.dt > 0 {
-            self.dt -= 1;
+    /// # Returns
+    ///
+    /// * `Ok(())` if all invariants hold.
+    /// * `Err(Chip8Error::SPError)` if the stack pointer is out of bounds.
+    /// * `Err(Chip8Error::PCError)` if the program counter is outside memory.
+    /// * `Err(Chip8Error::IndexError)` if the index register is outside memory.
+    /// * `Err(Chip8Error::InvalidKey)` with the key index if a key state is corrupted.
+    pub fn validate(&self) -> Result<(), Chip8Error> {
+        if self.sp as usize > self.stack.len() {
+            return Err(Chip8Error::SPError(self.sp));
         }
-        if self.st > 0 {
-            self.st -= 1;
+        if self.pc as usize >= self.memory.size() {
+            return Err(Chip8Error::PCError(self.pc));
+        }
+        if self.i as usize >= self.memory.size() {
+            return Err(Chip8Error::IndexError(self.i));
+        }
+        if let Some(index) = self.keyboard.iter().position(|&key| key > 1) {
+            return Err(Chip8Error::InvalidKey(index as u8));
         }
+        Ok(())
     }
 
-    /// Returns true if the sound timer is greater than 0, indicating a beep should be played.
+    /// Marks a range of memory as read-only.
     ///
-    /// The sound timer controls when the CHIP-8 system should produce its characteristic
-    /// beep sound. When the timer is non-zero, a continuous tone should be played.
-    /// When it reaches zero, the sound should stop.
+    /// Instructions writing into the range (`FX33`, `FX55`) then fail with
+    /// `Chip8Error::MemoryError(MemoryError::WriteProtected)`. This can be used
+    /// to catch ROMs that accidentally overwrite their own code, by protecting
+    /// the region the ROM was loaded into. Protection is cleared by
+    /// [`Chip8::reset()`], except for the font set when protected with
+    /// [`Chip8Builder::protect_font()`].
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// * `true` if sound should be playing (sound timer > 0)
-    /// * `false` if sound should be silent (sound timer = 0)
-    pub fn should_beep(&self) -> bool {
-        self.st > 0
+    /// * `range`: The range of addresses to protect.
+    pub fn set_readonly_range(&mut self, range: std::ops::Range<usize>) {
+        self.memory.set_readonly_range(range);
     }
 
-    /// Returns the current value of the delay timer.
+    /// Returns the whole RAM as a read-only slice.
     ///
-    /// The delay timer is an 8-bit countdown timer that decrements at 60Hz until
-    /// it reaches zero. Programs use it for timing delays, animations, and
-    /// synchronization. It can be set by the `FX15` instruction and read by
-    /// the `FX07` instruction.
+    /// This is convenient for memory viewers and full dumps; index the slice
+    /// for smaller views. The slice is [`Chip8::memory_len()`] bytes long.
+    pub fn memory(&self) -> &[u8] {
+        self.memory.get(..).unwrap_or_default()
+    }
+
+    /// Returns the size of the RAM in bytes.
+    pub fn memory_len(&self) -> usize {
+        self.memory.size()
+    }
+
+    /// Returns the odd addresses instructions were fetched from.
+    ///
+    /// Addresses are only recorded if the machine was built with
+    /// [`Chip8Builder::warn_on_odd_pc()`]. Each address appears once, in the
+    /// order it was first fetched from. The list is cleared on
+    /// [`Chip8::reset()`].
+    pub fn odd_pc_warnings(&self) -> &[u16] {
+        &self.odd_pc_warnings
+    }
+
+    /// Returns the highest memory address written since the last reset.
+    ///
+    /// Writes from loading the ROM and from instructions such as `FX55` are
+    /// counted, but not the built-in font set. This approximates how much of
+    /// the RAM a program uses, e.g. for a memory usage indicator.
     ///
     /// # Returns
     ///
-    /// The current delay timer value (0-255)
-    pub fn delay_timer(&self) -> u8 {
-        self.dt
+    /// The highest written address, or `0` if nothing has been written yet.
+    pub fn high_water_mark(&self) -> usize {
+        self.memory.high_water_mark()
     }
 
-    /// Returns the current value of the sound timer.
+    /// Returns a read-only slice of the framebuffer.
     ///
-    /// The sound timer is an 8-bit countdown timer that decrements at 60Hz until
-    /// it reaches zero. While non-zero, the CHIP-8 system should produce a beep
-    /// sound. It can be set by the `FX18` instruction.
+    /// The framebuffer represents the CHIP-8's monochrome display, 64x32 pixels
+    /// or 128x64 in high resolution mode (see [`Chip8::width()`] and
+    /// [`Chip8::height()`]). Each byte in the slice corresponds to a pixel,
+    /// with `1` representing a pixel that is on and `0` for a pixel that is
+    /// off. The data is stored in row-major order.
+    pub fn framebuffer(&self) -> &[u8] {
+        &self.framebuffer[..self.width() * self.height()]
+    }
+
+    /// Returns the number of pixels that are currently on.
     ///
-    /// # Returns
+    /// Only the active display area is counted (see [`Chip8::framebuffer()`]),
+    /// which makes this a quick assertion for rendering tests.
+    pub fn total_pixels_on(&self) -> usize {
+        self.framebuffer()
+            .iter()
+            .filter(|&&pixel| pixel != 0)
+            .count()
+    }
+
+    /// Returns `true` if every pixel of the display is off.
     ///
-    /// The current sound timer value (0-255)
-    pub fn sound_timer(&self) -> u8 {
-        self.st
+    /// Like [`Chip8::total_pixels_on()`], only the active display area is
+    /// checked. Frontends can use this to detect blank transition screens.
+    pub fn is_display_blank(&self) -> bool {
+        self.framebuffer().iter().all(|&pixel| pixel == 0)
     }
 
-    /// Returns true if the delay timer has reached zero (finished).
+    /// Returns the framebuffer packed into 8 pixels per byte.
     ///
-    /// This is a convenience method that's equivalent to `delay_timer() == 0`.
-    /// It's commonly used to check if a timed delay has completed.
+    /// Pixels are in the same row-major order as [`Chip8::framebuffer()`],
+    /// with the most significant bit of each byte holding the leftmost pixel.
+    /// Both display widths are multiples of 8, so every row starts on a byte
+    /// boundary and the result is `width * height / 8` bytes long.
     ///
-    /// # Returns
+    /// The framebuffer itself stays one byte per pixel, because
+    /// [`Chip8::framebuffer()`] and [`Chip8::framebuffer_mut()`] hand out
+    /// slices of it directly. This packed copy suits frontends that transfer
+    /// or store frames and want them 8 times smaller.
+    pub fn framebuffer_bits(&self) -> Vec<u8> {
+        self.framebuffer()
+            .chunks(8)
+            .map(|pixels| {
+                pixels
+                    .iter()
+                    .fold(0, |byte, &pixel| byte << 1 | (pixel & 1))
+            })
+            .collect()
+    }
+
+    /// Returns the display as a grayscale image.
     ///
-    /// * `true` if the delay timer is 0 (delay finished)
-    /// * `false` if the delay timer is still counting down
-    pub fn delay_timer_finished(&self) -> bool {
-        self.dt == 0
+    /// The image has the size of the current resolution, with lit pixels set
+    /// to 255 and unlit pixels to 0. It can be saved directly, e.g. with
+    /// `chip8.framebuffer_image().save("frame.png")`. Only available with the
+    /// `image` feature.
+    #[cfg(feature = "image")]
+    pub fn framebuffer_image(&self) -> image::GrayImage {
+        let width = self.width();
+        image::GrayImage::from_fn(width as u32, self.height() as u32, |x, y| {
+            let pixel = self.framebuffer[y as usize * width + x as usize];
+            image::Luma([if pixel != 0 { 255 } else { 0 }])
+        })
     }
 
-    /// Executes a single CHIP-8 instruction cycle.
+    /// Copies the framebuffer into a caller-owned buffer.
     ///
-    /// This involves fetching the opcode from memory at the program counter,
-    /// decoding it, and executing the corresponding operation. The program
-    /// counter is advanced accordingly.
+    /// This lets frontends reuse one buffer instead of allocating a copy of
+    /// [`Chip8::framebuffer()`] on every frame. Only the first
+    /// `width * height` bytes of `out` are written; a larger buffer can be
+    /// kept around for both display resolutions.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// * `Ok(())` on successful execution of the instruction.
-    /// * `Err(Chip8Error)` if an error occurs, such as fetching from an invalid
-    ///   memory address or executing an invalid opcode.
-    pub fn run(&mut self) -> Result<(), Chip8Error> {
-        let instruction = self.fetch()?;
-        self.execute_instruction(&instruction)
+    /// * `out`: The buffer to copy the pixels into.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::FrameBufferOverflow` with the required length if
+    /// `out` is too small to hold the framebuffer.
+    pub fn clone_framebuffer_into(&self, out: &mut [u8]) -> Result<(), Chip8Error> {
+        let framebuffer = self.framebuffer();
+        out.get_mut(..framebuffer.len())
+            .ok_or(Chip8Error::FrameBufferOverflow(framebuffer.len()))?
+            .copy_from_slice(framebuffer);
+        Ok(())
     }
 
-    /// Fetches the next instruction from memory at the current program counter (`pc`),
-    /// decodes it, and advances the `pc` by two bytes.
+    /// Renders the framebuffer as RGBA, upscaled by an integer factor.
     ///
-    /// # Returns
+    /// Each pixel becomes a `scale`x`scale` block of `fg` if it is on or `bg`
+    /// if it is off (nearest-neighbor scaling). The output is row-major with
+    /// 4 bytes per pixel, `width * scale` pixels wide and `height * scale`
+    /// pixels high. As with [`Chip8::clone_framebuffer_into()`], only the
+    /// first `width * scale * height * scale * 4` bytes of `out` are written.
     ///
-    /// * `Ok(Instructions)` containing the decoded instruction.
-    /// * `Err(Chip8Error::PCError)` if the `pc` is at or near the end of memory,
-    ///   making it impossible to fetch a full 2-byte instruction.
-    fn fetch(&mut self) -> Result<Instruction, Chip8Error> {
-        let instruction = self
-            .memory
-            .read_word(self.pc as usize)
-            .ok_or(Chip8Error::PCError(self.pc))?;
+    /// # Arguments
+    ///
+    /// * `scale`: The upscaling factor. A scale of 0 renders nothing.
+    /// * `fg`: The RGBA color of pixels that are on.
+    /// * `bg`: The RGBA color of pixels that are off.
+    /// * `out`: The buffer to render into.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Chip8Error::FrameBufferOverflow` with the required length if
+    /// `out` is too small to hold the scaled image.
+    pub fn render_scaled_rgba(
+        &self,
+        scale: usize,
+        fg: [u8; 4],
+        bg: [u8; 4],
+        out: &mut [u8],
+    ) -> Result<(), Chip8Error> {
+        let scaled_width = self.width() * scale;
+        let len = scaled_width * self.height() * scale * 4;
+        let out = out
+            .get_mut(..len)
+            .ok_or(Chip8Error::FrameBufferOverflow(len))?;
+
+        for (index, pixel) in out.chunks_exact_mut(4).enumerate() {
+            let x = index % scaled_width / scale;
+            let y = index / scaled_width / scale;
+            let color = if self.framebuffer[y * self.width() + x] != 0 {
+                fg
+            } else {
+                bg
+            };
+            pixel.copy_from_slice(&color);
+        }
+        Ok(())
+    }
 
-        self.pc = self.pc.checked_add(2).ok_or(Chip8Error::PCError(self.pc))?;
-        Ok(Instruction::new(instruction))
+    /// Returns a mutable slice of the framebuffer.
+    ///
+    /// This allows frontends to post-process the display in place, e.g. for
+    /// CRT-style effects. The layout is the same as [`Chip8::framebuffer()`].
+    ///
+    /// Writing through this slice bypasses the `DXYN` drawing semantics: it
+    /// does not XOR pixels, detect collisions or touch VF, and it does not set
+    /// the display updated flag. Call [`Chip8::mark_display_dirty()`] after
+    /// modifying pixels if the change should trigger a redraw.
+    pub fn framebuffer_mut(&mut self) -> &mut [u8] {
+        let len = self.width() * self.height();
+        &mut self.framebuffer[..len]
     }
 
-    /// Pushes the program counter (`pc`) onto the stack.
+    /// Returns the color index of a pixel.
     ///
-    /// Increments the stack pointer (`sp`) after pushing.
+    /// The index combines the bits of the display planes: bit 0 comes from the
+    /// first plane and bit 1 from the second one (XO-CHIP). The default
+    /// 4-color palette maps the indices as follows:
+    ///
+    /// | Index | Planes       | Color      |
+    /// |-------|--------------|------------|
+    /// | 0     | none         | black      |
+    /// | 1     | first        | white      |
+    /// | 2     | second       | light gray |
+    /// | 3     | both         | dark gray  |
+    ///
+    /// Only the first plane is implemented for now, so the index is currently
+    /// always 0 or 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`: The column of the pixel.
+    /// * `y`: The row of the pixel.
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the push was successful.
-    /// * `Err(Chip8Error::SPOverflow)` if the stack pointer would overflow.
-    /// * `Err(Chip8Error::SPError)` if the stack pointer is out of bounds.
-    fn push_stack(&mut self) -> Result<(), Chip8Error> {
-        if let Some(memory) = self.stack.get_mut(self.sp as usize) {
-            *memory = self.pc;
-            self.sp = self
-                .sp
-                .checked_add(1)
-                .ok_or(Chip8Error::SPOverflow(self.sp))?;
-        } else {
-            return Err(Chip8Error::SPError(self.sp));
+    /// * `Some(u8)` with the color index (0-3).
+    /// * `None` if the coordinates are outside the display at the current resolution.
+    pub fn color_at(&self, x: usize, y: usize) -> Option<u8> {
+        if x >= self.width() || y >= self.height() {
+            return None;
         }
-        Ok(())
+        self.framebuffer
+            .get(y * self.width() + x)
+            .map(|&pixel| pixel & 1)
     }
 
-    /// Pops a value from the stack into the program counter (`pc`).
+    /// Reads a sprite from memory as pixels, without drawing it.
     ///
-    /// Decrements the stack pointer (`sp`) before popping.
+    /// The sprite is decoded the same way `DXYN` reads it: one byte per row,
+    /// most significant bit leftmost.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: The address of the first sprite byte, usually I.
+    /// * `height`: The number of rows in the sprite.
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the pop was successful.
-    /// * `Err(Chip8Error::SPOverflow)` if the stack pointer would underflow.
-    /// * `Err(Chip8Error::SPError)` if the stack pointer is out of bounds.
-    fn pop_stack(&mut self) -> Result<(), Chip8Error> {
+    /// * `Some(Vec<bool>)` with `height * 8` pixels in row-major order, where
+    ///   `true` is a lit pixel.
+    /// * `None` if the sprite extends past the end of memory.
+    pub fn sprite_preview(&self, addr: usize, height: u8) -> Option<Vec<bool>> {
+        let end = addr.checked_add(height as usize)?;
+        let bytes = (addr..end)
+            .map(|address| self.memory.read_byte(address))
+            .collect::<Option<Vec<u8>>>()?;
+        Some(
+            bytes
+                .iter()
+                .flat_map(|byte| (0..8).rev().map(move |bit| byte >> bit & 1 == 1))
+                .collect(),
+        )
+    }
+
+    /// Returns the display packed into one `u64` per row.
+    ///
+    /// Bit 63 holds the leftmost pixel of the row and bit 0 the rightmost.
+    /// This compact form is handy for hashing frames or comparing test output.
+    /// In high resolution mode, a 128-pixel row doesn't fit in a `u64` and is
+    /// split over two consecutive elements, left half first.
+    ///
+    /// # Returns
+    ///
+    /// The packed rows, from top to bottom.
+    pub fn display_as_rows(&self) -> Vec<u64> {
+        self.framebuffer()
+            .chunks_exact(64)
+            .map(|pixels| {
+                pixels
+                    .iter()
+                    .fold(0u64, |row, &pixel| (row << 1) | u64::from(pixel & 1))
+            })
+            .collect()
+    }
+
+    /// Returns the width of the display at the current resolution.
+    ///
+    /// # Returns
+    ///
+    /// 64 pixels, or 128 pixels in high resolution mode.
+    pub fn width(&self) -> usize {
+        if self.hires {
+            HIRES_FRAMEBUFFER_WIDTH
+        } else {
+            FRAMEBUFFER_WIDTH
+        }
+    }
+
+    /// Returns the height of the display at the current resolution.
+    ///
+    /// # Returns
+    ///
+    /// 32 pixels, or 64 pixels in high resolution mode.
+    pub fn height(&self) -> usize {
+        if self.hires {
+            HIRES_FRAMEBUFFER_HEIGHT
+        } else {
+            FRAMEBUFFER_HEIGHT
+        }
+    }
+
+    /// Marks the display as updated so that the UI layer redraws it.
+    ///
+    /// This is intended for use after modifying pixels via
+    /// [`Chip8::framebuffer_mut()`].
+    pub fn mark_display_dirty(&mut self) {
+        self.display_updated = true;
+    }
+
+    /// Checks if the display has been updated since the last check.
+    ///
+    /// This flag is set to `true` by instructions that modify the framebuffer,
+    /// such as `00E0` (clear screen) and `DXYN` (draw sprite). The UI layer
+    /// should check this flag each frame to determine if it needs to redraw
+    /// the screen.
+    pub fn is_display_updated(&self) -> bool {
+        self.display_updated
+    }
+
+    /// Clears the display updated flag.
+    ///
+    /// This should be called by the UI layer after it has redrawn the screen
+    /// based on the `is_display_updated` flag.
+    pub fn clear_display_updated_flag(&mut self) {
+        self.display_updated = false;
+    }
+
+    /// Returns the display updated flag and clears it.
+    ///
+    /// This combines [`Chip8::is_display_updated()`] and
+    /// [`Chip8::clear_display_updated_flag()`], so a frontend that redraws
+    /// whenever this returns `true` can't forget to clear the flag.
+    pub fn take_display_updated(&mut self) -> bool {
+        std::mem::take(&mut self.display_updated)
+    }
+
+    /// Returns the quirks currently applied by the machine.
+    pub fn quirks(&self) -> &Quirks {
+        &self.quirks
+    }
+
+    /// Replaces the quirks applied by the machine.
+    ///
+    /// The new quirks take effect from the next executed instruction, so they
+    /// can be changed while a program is running. Quirks are configuration and
+    /// survive [`Chip8::reset()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `quirks`: The quirks to apply.
+    pub fn set_quirks(&mut self, quirks: Quirks) {
+        self.quirks = quirks;
+    }
+
+    /// Registers a callback invoked whenever a sprite draw causes a collision.
+    ///
+    /// The hook fires once per `DXYN` instruction that sets VF to 1, after the
+    /// sprite has been drawn. Frontends can use it to react to collisions beyond
+    /// what the ROM itself does. The hook survives [`Chip8::reset()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `f`: The callback to invoke on collision.
+    pub fn set_collision_hook(&mut self, f: Box<dyn FnMut() + Send>) {
+        self.collision_hook = Some(f);
+    }
+
+    /// Registers a callback invoked for every byte written to memory by an instruction.
+    ///
+    /// The hook receives the address and the new value of each byte, in
+    /// address order, after the instruction has written them (`FX33` reports
+    /// three bytes, `FX55` reports x + 1). Writes made by the host, such as
+    /// [`Chip8::load_rom()`], aren't reported. The hook survives
+    /// [`Chip8::reset()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `f`: The callback to invoke with `(address, value)` for each byte.
+    pub fn set_memory_write_hook(&mut self, f: Box<dyn FnMut(usize, u8) + Send>) {
+        self.memory_write_hook = Some(f);
+    }
+
+    /// Replaces the state of the whole keypad at once.
+    ///
+    /// Bit `n` of the mask corresponds to key `n` (0-F): a set bit marks the key
+    /// as pressed, a cleared bit as released. This is convenient for frontends
+    /// that sample their input once per frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `keypad`: The bitmask of pressed keys.
+    pub fn set_keyboard_state(&mut self, keypad: u16) {
+        for (index, key) in self.keyboard.iter_mut().enumerate() {
+            *key = ((keypad >> index) & 1) as u8;
+        }
+    }
+
+    /// Replaces the state of the whole keypad from an array.
+    ///
+    /// This is the array counterpart of [`Chip8::set_keyboard_state()`]:
+    /// `keys[n]` is `true` if key `n` (0-F) is pressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys`: The state of each key.
+    pub fn set_keyboard(&mut self, keys: [bool; 16]) {
+        self.keyboard = keys.map(u8::from);
+    }
+
+    /// Returns whether a single key is pressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_index`: The index of the key (0-15).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key is pressed, `false` if it's released or the index is
+    /// out of range.
+    pub fn key_state(&self, key_index: u8) -> bool {
+        self.keyboard
+            .get(key_index as usize)
+            .is_some_and(|&key| key != 0)
+    }
+
+    /// Returns the state of the whole keypad as a bitmask.
+    ///
+    /// # Returns
+    ///
+    /// A mask where bit `n` is set if key `n` (0-F) is currently pressed.
+    pub fn keyboard_state(&self) -> u16 {
+        self.keyboard
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (index, &key)| {
+                mask | (u16::from(key != 0) << index)
+            })
+    }
+
+    /// Schedules a key event to be applied at a given cycle.
+    ///
+    /// The event is applied by [`Chip8::run()`] and [`Chip8::step()`] right
+    /// before executing the instruction whose cycle number (see
+    /// [`Chip8::cycle_count()`]) is `at_cycle`, or immediately on the next call
+    /// if that cycle has already passed. Replaying a recorded sequence of events this way makes input
+    /// deterministic, independent of wall-clock timing.
+    ///
+    /// # Arguments
+    ///
+    /// * `at_cycle`: The cycle at which the event takes effect.
+    /// * `key`: The index of the key (0-15). Any value outside this range will
+    ///   be ignored when the event is applied.
+    /// * `pressed`: `true` for a key press, `false` for a key release.
+    pub fn queue_key_event(&mut self, at_cycle: u64, key: u8, pressed: bool) {
+        // Keep events ordered by cycle, preserving insertion order for ties
+        let index = self
+            .key_events
+            .partition_point(|&(cycle, _, _)| cycle <= at_cycle);
+        self.key_events.insert(index, (at_cycle, key, pressed));
+    }
+
+    /// Returns the number of random bytes drawn by `CXNN` since the last reset.
+    ///
+    /// Two runs of a seeded machine (see [`Chip8Builder::rng_seed()`]) with the
+    /// same input draw the same number of bytes, so a difference in this count
+    /// signals that a replay went out of sync.
+    pub fn random_draws(&self) -> u64 {
+        self.random_draws
+    }
+
+    /// Returns the number of instructions executed since the last reset.
+    pub fn cycle_count(&self) -> u64 {
+        self.cycle_count
+    }
+
+    /// Presses a key on the CHIP-8 keypad.
+    ///
+    /// This is the type-safe counterpart of [`Chip8::key_press()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to press.
+    pub fn press(&mut self, key: Key) {
+        self.key_press(key.into());
+    }
+
+    /// Releases a key on the CHIP-8 keypad.
+    ///
+    /// This is the type-safe counterpart of [`Chip8::key_release()`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to release.
+    pub fn release(&mut self, key: Key) {
+        self.key_release(key.into());
+    }
+
+    /// Returns `true` if a key was pressed during the current frame.
+    ///
+    /// Frames are delimited by [`Chip8::tick_timers()`]: the key is
+    /// "just pressed" if it is held now but wasn't at the last timer tick.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_index`: The index of the key (0-15). Out-of-range indices
+    ///   return `false`.
+    pub fn key_just_pressed(&self, key_index: u8) -> bool {
+        self.key_state(key_index) && self.previous_keyboard[key_index as usize] == 0
+    }
+
+    /// Returns `true` if a key was released during the current frame.
+    ///
+    /// This is the counterpart of [`Chip8::key_just_pressed()`]: the key was
+    /// held at the last timer tick but isn't anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_index`: The index of the key (0-15). Out-of-range indices
+    ///   return `false`.
+    pub fn key_just_released(&self, key_index: u8) -> bool {
+        self.previous_keyboard
+            .get(key_index as usize)
+            .is_some_and(|&key| key != 0)
+            && !self.key_state(key_index)
+    }
+
+    /// Simulates a key press on the CHIP-8 keypad.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_index`: The index of the key to press (0-15). Any value outside
+    ///   this range will be ignored.
+    pub fn key_press(&mut self, key_index: u8) {
+        if let Some(key) = self.keyboard.get_mut(key_index as usize) {
+            *key = 1;
+        }
+    }
+
+    /// Simulates a key release on the CHIP-8 keypad.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_index`: The index of the key to release (0-15). Any value outside
+    ///   this range will be ignored.
+    pub fn key_release(&mut self, key_index: u8) {
+        if let Some(key) = self.keyboard.get_mut(key_index as usize) {
+            *key = 0;
+        }
+    }
+
+    /// Decrements both delay and sound timers by 1 if they are greater than 0.
+    ///
+    /// This function should be called at exactly 60Hz frequency to maintain proper
+    /// timing behavior that CHIP-8 programs expect. The CHIP-8 specification
+    /// defines that both timers decrement at this rate until they reach zero.
+    ///
+    /// # Timer Behavior
+    ///
+    /// - **Delay Timer (DT)**: Used by programs for timing delays and synchronization
+    /// - **Sound Timer (ST)**: Controls the duration of the beep sound
+    ///
+    /// # Usage
+    ///
+    /// This function should typically be called in your main emulation loop at
+    /// a consistent 60Hz interval (approximately every 16.67ms).
+    ///
+    /// # Note
+    ///
+    /// This function does not handle timing automatically. It is the caller's
+    /// responsibility to ensure it is called at the correct frequency for
+    /// accurate CHIP-8 timing behavior.
+    ///
+    /// Each call also marks a frame boundary for
+    /// [`Chip8::key_just_pressed()`] and [`Chip8::key_just_released()`].
+    pub fn tick_timers(&mut self) {
+        let was_beeping = self.should_beep();
+        if self.dt > 0 {
+            self.dt -= 1;
+        }
+        if self.st > 0 {
+            self.st -= 1;
+        }
+        self.update_sound_edge(was_beeping);
+        self.previous_keyboard = self.keyboard;
+    }
+
+    /// Returns the number of audio samples the remaining beep lasts.
+    ///
+    /// The sound timer counts down at 60 Hz, so the beep lasts `ST / 60`
+    /// seconds. Audio backends can use this to queue a buffer of exactly the
+    /// right length. The result is rounded to the nearest sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_rate`: The output sample rate in Hz.
+    pub fn beep_sample_count(&self, sample_rate: u32) -> usize {
+        (self.st as f64 / 60.0 * sample_rate as f64).round() as usize
+    }
+
+    /// Returns the last change of the beeper state since the previous call.
+    ///
+    /// Audio backends can poll this after running instructions and ticking
+    /// the timers instead of comparing [`Chip8::should_beep()`] between calls.
+    /// A start edge is recorded when `FX18` sets the sound timer while it is
+    /// zero, and a stop edge when [`Chip8::tick_timers()`] or `FX18` brings it
+    /// back to zero. The edge is cleared once reported.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(SoundEdge)` with the most recent edge, if the beeper changed
+    ///   since the previous call.
+    /// * `None` if the beeper didn't change.
+    pub fn sound_edge(&mut self) -> Option<SoundEdge> {
+        self.sound_edge.take()
+    }
+
+    /// Records a sound edge if the beeper state differs from `was_beeping`.
+    pub(crate) fn update_sound_edge(&mut self, was_beeping: bool) {
+        match (was_beeping, self.should_beep()) {
+            (false, true) => self.sound_edge = Some(SoundEdge::Start),
+            (true, false) => self.sound_edge = Some(SoundEdge::Stop),
+            _ => {}
+        }
+    }
+
+    /// Returns true if the sound timer is greater than 0, indicating a beep should be played.
+    ///
+    /// The sound timer controls when the CHIP-8 system should produce its characteristic
+    /// beep sound. When the timer is non-zero, a continuous tone should be played.
+    /// When it reaches zero, the sound should stop.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if sound should be playing (sound timer > 0)
+    /// * `false` if sound should be silent (sound timer = 0)
+    pub fn should_beep(&self) -> bool {
+        self.st > 0
+    }
+
+    /// Returns the current value of the delay timer.
+    ///
+    /// The delay timer is an 8-bit countdown timer that decrements at 60Hz until
+    /// it reaches zero. Programs use it for timing delays, animations, and
+    /// synchronization. It can be set by the `FX15` instruction and read by
+    /// the `FX07` instruction.
+    ///
+    /// # Returns
+    ///
+    /// The current delay timer value (0-255)
+    pub fn delay_timer(&self) -> u8 {
+        self.dt
+    }
+
+    /// Returns the current value of the sound timer.
+    ///
+    /// The sound timer is an 8-bit countdown timer that decrements at 60Hz until
+    /// it reaches zero. While non-zero, the CHIP-8 system should produce a beep
+    /// sound. It can be set by the `FX18` instruction.
+    ///
+    /// # Returns
+    ///
+    /// The current sound timer value (0-255)
+    pub fn sound_timer(&self) -> u8 {
+        self.st
+    }
+
+    /// Returns a copy of the general-purpose registers V0-VF.
+    ///
+    /// The copy is owned, so it can be stored, e.g. in a debugger's history,
+    /// and compared with later values without borrowing the machine.
+    ///
+    /// # Returns
+    ///
+    /// The values of V0 to VF, indexed by register number.
+    pub fn registers_snapshot(&self) -> [u8; 16] {
+        self.registers
+    }
+
+    /// Returns true if the delay timer has reached zero (finished).
+    ///
+    /// This is a convenience method that's equivalent to `delay_timer() == 0`.
+    /// It's commonly used to check if a timed delay has completed.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the delay timer is 0 (delay finished)
+    /// * `false` if the delay timer is still counting down
+    pub fn delay_timer_finished(&self) -> bool {
+        self.dt == 0
+    }
+
+    /// Executes a single CHIP-8 instruction cycle.
+    ///
+    /// This involves fetching the opcode from memory at the program counter,
+    /// decoding it, and executing the corresponding operation. The program
+    /// counter is advanced accordingly. Key events queued for the current cycle
+    /// with [`Chip8::queue_key_event()`] are applied before the fetch.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` on successful execution of the instruction, or without doing
+    ///   anything if the machine is halted (see [`Chip8::is_halted()`]).
+    /// * `Err(Chip8Error)` if an error occurs, such as fetching from an invalid
+    ///   memory address or executing an invalid opcode.
+    pub fn run(&mut self) -> Result<(), Chip8Error> {
+        self.run_cycle().map(|_| ())
+    }
+
+    /// Executes a single instruction cycle and returns the executed instruction.
+    ///
+    /// This behaves exactly like [`Chip8::run()`], but hands back the
+    /// fetched [`Instruction`], so that simple single-step loops such as a
+    /// tracer or debugger can inspect what ran without installing a hook.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Instruction)` with the executed instruction. If the machine is
+    ///   halted, nothing is executed and the `00FD` instruction that halted
+    ///   it is returned.
+    /// * `Err(Chip8Error)` if an error occurs, as for [`Chip8::run()`].
+    pub fn run_traced(&mut self) -> Result<Instruction, Chip8Error> {
+        Ok(self
+            .run_cycle()?
+            .unwrap_or_else(|| Instruction::new(0x00FD)))
+    }
+
+    /// Runs one instruction cycle for [`Chip8::run()`] and [`Chip8::run_traced()`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Instruction))` with the executed instruction.
+    /// * `Ok(None)` if the machine is halted and nothing was executed.
+    /// * `Err(Chip8Error)` if fetching or executing the instruction fails.
+    fn run_cycle(&mut self) -> Result<Option<Instruction>, Chip8Error> {
+        if self.halted {
+            return Ok(None);
+        }
+
+        // Applied before the undo snapshot: the queue isn't part of the state,
+        // so replaying the cycle after `step_back` must still see the events
+        self.apply_due_key_events();
+
+        // Only recorded once the fetch succeeds, as a failed fetch runs nothing to undo
+        let undo = (self.history_depth > 0).then(|| (self.snapshot(), self.rng.clone()));
+
+        if self.warn_on_odd_pc && self.pc % 2 == 1 && !self.odd_pc_warnings.contains(&self.pc) {
+            self.odd_pc_warnings.push(self.pc);
+        }
+
+        if self.pc_history_depth > 0 {
+            if self.pc_history.len() == 2 * self.pc_history_depth {
+                self.pc_history.drain(..self.pc_history_depth);
+            }
+            self.pc_history.push(self.pc);
+        }
+
+        let instruction = self.fetch()?;
+        if let Some(entry) = undo {
+            if self.history.len() == self.history_depth {
+                self.history.pop_front();
+            }
+            self.history.push_back(entry);
+        }
+        self.cycle_count += 1;
+        self.execute_instruction(&instruction)?;
+        Ok(Some(instruction))
+    }
+
+    /// Applies the queued key events whose cycle has been reached.
+    ///
+    /// # Side Effects
+    ///
+    /// * Pops the due events from the queue filled by
+    ///   [`Chip8::queue_key_event()`] and updates the keyboard state.
+    fn apply_due_key_events(&mut self) {
+        while let Some(&(cycle, key, pressed)) = self.key_events.front() {
+            if cycle > self.cycle_count {
+                break;
+            }
+            self.key_events.pop_front();
+            if pressed {
+                self.key_press(key);
+            } else {
+                self.key_release(key);
+            }
+        }
+    }
+
+    /// Executes a single instruction cycle, returning control while blocked on input.
+    ///
+    /// With [`Chip8::run()`], `FX0A` waits for a key by rewinding the program
+    /// counter, so the instruction is executed again on every cycle. `step`
+    /// instead reports [`RunStatus::WaitingForKey`] and doesn't execute
+    /// anything until a key is pressed, which lets asynchronous frontends stop
+    /// scheduling CPU cycles until input arrives. Likewise, with the
+    /// `display_wait` quirk a `DXYN` reports [`RunStatus::WaitingForVblank`],
+    /// and frame loops stop executing until the next frame.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RunStatus::WaitingForKey)` if the program is blocked on `FX0A`.
+    /// * `Ok(RunStatus::Halted)` if the program halted.
+    /// * `Ok(RunStatus::WaitingForVblank)` if a sprite was drawn and the
+    ///   `display_wait` quirk is enabled.
+    /// * `Ok(RunStatus::Running)` if execution can continue.
+    /// * `Err(Chip8Error)` if the instruction fails, as for [`Chip8::run()`].
+    pub fn step(&mut self) -> Result<RunStatus, Chip8Error> {
+        // A queued press can be what releases an FX0A wait
+        self.apply_due_key_events();
+        if self.waiting_for_key.is_some() && !self.keyboard.contains(&1) {
+            return Ok(RunStatus::WaitingForKey);
+        }
+        let instruction = self.run_traced()?;
+
+        Ok(if self.halted {
+            RunStatus::Halted
+        } else if self.waiting_for_key.is_some() {
+            RunStatus::WaitingForKey
+        } else if self.quirks.display_wait && instruction.instruction() == 0xD {
+            RunStatus::WaitingForVblank
+        } else {
+            RunStatus::Running
+        })
+    }
+
+    /// Returns `true` if the program halted by executing `00FD`.
+    ///
+    /// A halted machine ignores calls to [`Chip8::run()`] until it is reset.
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Returns `true` if the most recent `DXYN` turned off any pixel.
+    ///
+    /// This is the collision flag that `DXYN` writes to VF, tracked
+    /// separately so it stays available after other instructions overwrite VF.
+    pub fn last_draw_collided(&self) -> bool {
+        self.last_draw_collided
+    }
+
+    /// Returns the number of `DXYN` draws that collided since the last reset.
+    ///
+    /// A draw counts once, no matter how many pixels it turned off. See
+    /// [`Chip8::last_draw_collided()`].
+    pub fn collision_count(&self) -> u64 {
+        self.collision_count
+    }
+
+    /// Returns the register a pending `FX0A` will store the key in.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(x)` if the program is blocked on `FX0A` waiting for a key
+    ///   press, where `x` is the index of the target register Vx.
+    /// * `None` if the program isn't waiting for a key.
+    pub fn pending_key_wait(&self) -> Option<usize> {
+        self.waiting_for_key
+    }
+
+    /// Runs the program until it halts, starts spinning, or a cycle limit is reached.
+    ///
+    /// Execution stops when the program executes `00FD`, or when the next
+    /// instruction is a jump to itself (`1NNN` with NNN equal to its own
+    /// address), the usual way for test ROMs to signal completion. The spinning
+    /// jump itself is not executed. This is meant for automated runs of
+    /// self-terminating ROMs.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_cycles`: The maximum number of instructions to execute.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of instructions executed.
+    /// * `Err(Chip8Error)` if an instruction fails.
+    pub fn run_until_halt(&mut self, max_cycles: usize) -> Result<usize, Chip8Error> {
+        for cycles in 0..max_cycles {
+            if self.halted || self.is_spinning() {
+                return Ok(cycles);
+            }
+            self.run()?;
+        }
+        Ok(max_cycles)
+    }
+
+    /// Runs the program until it halts, starts spinning, or a time limit is reached.
+    ///
+    /// This is [`Chip8::run_until_halt()`] with a wall-clock limit instead of
+    /// a cycle limit, so that a ROM that never terminates can't block an
+    /// interactive caller. To keep the overhead low, the clock is only read
+    /// every 1024 instructions, so the call may overrun `timeout` by the time
+    /// that many instructions take.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout`: The maximum time to run for.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of instructions executed.
+    /// * `Err(Chip8Error)` if an instruction fails.
+    pub fn run_until_halt_within(&mut self, timeout: Duration) -> Result<usize, Chip8Error> {
+        let deadline = Instant::now() + timeout;
+        let mut cycles = 0;
+        loop {
+            let executed = self.run_until_halt(DEADLINE_CHECK_INTERVAL)?;
+            cycles += executed;
+            if executed < DEADLINE_CHECK_INTERVAL || Instant::now() >= deadline {
+                return Ok(cycles);
+            }
+        }
+    }
+
+    /// Runs one 60 Hz frame worth of instructions.
+    ///
+    /// Executes up to `cycles` instructions with [`Chip8::step()`], stopping
+    /// early if the machine halts or blocks on `FX0A`. When the `display_wait`
+    /// quirk is enabled (see [`Quirks`]), the frame also ends right after the
+    /// first `DXYN`, and the rest of the frame is idle, as the COSMAC VIP
+    /// waited for the vertical blank before drawing. Timers are not touched;
+    /// call [`Chip8::tick_timers()`] once per frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `cycles`: The maximum number of instructions to execute this frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error raised by an instruction, as for
+    /// [`Chip8::run()`]; the rest of the frame is not executed.
+    pub fn run_frame(&mut self, cycles: usize) -> Result<(), Chip8Error> {
+        for _ in 0..cycles {
+            if self.step()? != RunStatus::Running {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the next instruction is a jump to itself.
+    fn is_spinning(&self) -> bool {
+        self.fetch_at(self.pc)
+            .and_then(|instruction| instruction.decode())
+            .is_ok_and(|op| op == DecodedOp::Jump(self.pc))
+    }
+
+    /// Executes a single instruction cycle, skipping over invalid opcodes.
+    ///
+    /// This behaves like [`Chip8::run()`], except that an unknown opcode is
+    /// treated as a recoverable error: the program counter has already moved
+    /// past it during fetch, so execution can simply continue with the next
+    /// instruction. All other errors are still propagated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if the instruction was executed.
+    /// * `Ok(false)` if the instruction was an invalid opcode and was skipped.
+    /// * `Err(Chip8Error)` for any other error, such as an invalid memory access.
+    pub fn run_skip_invalid(&mut self) -> Result<bool, Chip8Error> {
+        match self.run() {
+            Ok(()) => Ok(true),
+            Err(Chip8Error::InvalidOpCode(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Enables the undo history used by [`Chip8::step_back()`].
+    ///
+    /// When enabled, a full copy of the machine state is recorded before each
+    /// call to [`Chip8::run()`], keeping at most `depth` entries. Recording a
+    /// copy per instruction has a noticeable cost, so the history is disabled
+    /// by default. Any previously recorded history is discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth`: The maximum number of instructions that can be undone.
+    ///   A depth of `0` disables the history.
+    pub fn enable_history(&mut self, depth: usize) {
+        self.history_depth = depth;
+        self.history.clear();
+    }
+
+    /// Enables recording the addresses of recently executed instructions.
+    ///
+    /// The program counter is recorded on every fetch, including one that
+    /// fails, which makes the last few addresses before a crash available
+    /// through [`Chip8::pc_history()`]. This is much cheaper than the full
+    /// state history of [`Chip8::enable_history()`]. Any previously recorded
+    /// addresses are discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth`: The maximum number of addresses kept. A depth of `0`
+    ///   disables the recording.
+    pub fn enable_pc_history(&mut self, depth: usize) {
+        self.pc_history_depth = depth;
+        self.pc_history = Vec::with_capacity(2 * depth);
+    }
+
+    /// Returns the addresses of the most recently fetched instructions.
+    ///
+    /// # Returns
+    ///
+    /// Up to the depth set with [`Chip8::enable_pc_history()`] addresses,
+    /// oldest first. The slice is empty if the recording is disabled.
+    pub fn pc_history(&self) -> &[u16] {
+        let start = self.pc_history.len().saturating_sub(self.pc_history_depth);
+        &self.pc_history[start..]
+    }
+
+    /// Undoes the most recently executed instruction.
+    ///
+    /// Restores the state recorded before the last call to [`Chip8::run()`],
+    /// including the random number generator, so re-executing a `CXNN`
+    /// draws the same byte again. The history must have been enabled with
+    /// [`Chip8::enable_history()`].
+    ///
+    /// # Returns
+    ///
+    /// * `true` if an instruction was undone.
+    /// * `false` if the history is empty or disabled.
+    pub fn step_back(&mut self) -> bool {
+        match self.history.pop_back() {
+            Some((state, rng)) => {
+                self.restore(state);
+                self.rng = rng;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs up to `cpu_cycles` instructions and then ticks the timers once.
+    ///
+    /// This is a combined step for minimal frontends that don't need a separate
+    /// driver: calling it at 60Hz with the desired number of instructions per
+    /// frame gives a complete emulation loop. The instructions run as one
+    /// [`Chip8::run_frame()`], so execution stops early for the frame if the
+    /// program is blocked on `FX0A` waiting for a key press, or after a `DXYN`
+    /// with the `display_wait` quirk; the timers are still ticked in that case.
+    ///
+    /// # Arguments
+    ///
+    /// * `cpu_cycles`: The maximum number of instructions to execute.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the instructions executed and the timers were ticked.
+    /// * `Err(Chip8Error)` if an instruction fails. The timers are not ticked.
+    pub fn tick_and_run(&mut self, cpu_cycles: usize) -> Result<(), Chip8Error> {
+        self.run_frame(cpu_cycles)?;
+        self.tick_timers();
+        Ok(())
+    }
+
+    /// Runs the machine for a number of frames, invoking a callback after each one.
+    ///
+    /// Each frame executes up to `cycles_per_frame` instructions and ticks the
+    /// timers once (see [`Chip8::tick_and_run()`]), then passes the framebuffer
+    /// to `on_frame`. This is useful for recording a ROM's output, e.g. to
+    /// export a GIF or video.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames`: The number of frames to run.
+    /// * `cycles_per_frame`: The maximum number of instructions per frame.
+    /// * `on_frame`: Callback receiving the framebuffer at the end of each frame.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all frames ran successfully.
+    /// * `Err(Chip8Error)` if an instruction fails. No further frames are run.
+    pub fn run_frames(
+        &mut self,
+        frames: usize,
+        cycles_per_frame: usize,
+        mut on_frame: impl FnMut(&[u8]),
+    ) -> Result<(), Chip8Error> {
+        for _ in 0..frames {
+            self.tick_and_run(cycles_per_frame)?;
+            on_frame(self.framebuffer());
+        }
+        Ok(())
+    }
+
+    /// Reads and decodes the instruction at an arbitrary address.
+    ///
+    /// This performs the same bounds checks as the regular fetch cycle but
+    /// doesn't modify any state, so a debugger can decode any address without
+    /// disturbing the running program.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr`: The address of the instruction's first byte.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Instruction)` containing the decoded instruction.
+    /// * `Err(Chip8Error::PCError)` if `addr` is at or near the end of memory,
+    ///   making it impossible to read a full 2-byte instruction.
+    pub fn fetch_at(&self, addr: u16) -> Result<Instruction, Chip8Error> {
+        let opcode = self
+            .memory
+            .read_word(addr as usize)
+            .ok_or(Chip8Error::PCError(addr))?;
+        Ok(Instruction::new(opcode))
+    }
+
+    /// Fetches the next instruction from memory at the current program counter (`pc`),
+    /// decodes it, and advances the `pc` by two bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Instructions)` containing the decoded instruction.
+    /// * `Err(Chip8Error::PCError)` if the `pc` is at or near the end of memory,
+    ///   making it impossible to fetch a full 2-byte instruction.
+    fn fetch(&mut self) -> Result<Instruction, Chip8Error> {
+        let instruction = self.fetch_at(self.pc)?;
+
+        self.pc = self.pc.checked_add(2).ok_or(Chip8Error::PCError(self.pc))?;
+        Ok(instruction)
+    }
+
+    /// Pushes the program counter (`pc`) onto the stack.
+    ///
+    /// Increments the stack pointer (`sp`) after pushing.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the push was successful.
+    /// * `Err(Chip8Error::SPOverflow)` if the stack pointer would overflow.
+    /// * `Err(Chip8Error::SPError)` if the stack pointer is out of bounds.
+    fn push_stack(&mut self) -> Result<(), Chip8Error> {
+        if let Some(memory) = self.stack.get_mut(self.sp as usize) {
+            *memory = self.pc;
+            self.sp = self
+                .sp
+                .checked_add(1)
+                .ok_or(Chip8Error::SPOverflow(self.sp))?;
+        } else {
+            return Err(Chip8Error::SPError(self.sp));
+        }
+        Ok(())
+    }
+
+    /// Pops a value from the stack into the program counter (`pc`).
+    ///
+    /// Decrements the stack pointer (`sp`) before popping.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the pop was successful.
+    /// * `Err(Chip8Error::SPOverflow)` if the stack pointer would underflow.
+    /// * `Err(Chip8Error::SPError)` if the stack pointer is out of bounds.
+    fn pop_stack(&mut self) -> Result<(), Chip8Error> {
         self.sp = self
             .sp
             .checked_sub(1)
@@ -447,6 +1846,24 @@ impl Chip8 {
     }
 }
 
+/// Returns the width of the framebuffer.
+///
+/// # Returns
+///
+/// The width of the framebuffer. 64 pixels.
+pub fn framebuffer_width() -> usize {
+    FRAMEBUFFER_WIDTH
+}
+
+/// Returns the height of the framebuffer.
+///
+/// # Returns
+///
+/// The height of the framebuffer. 32 pixels.
+pub fn framebuffer_height() -> usize {
+    FRAMEBUFFER_HEIGHT
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -467,45 +1884,533 @@ mod tests {
     fn test_new() {
         let chip8 = Chip8::new().unwrap();
 
-        // Verify initial state
-        assert_eq!(chip8.pc, 0x200);
-        assert_eq!(chip8.sp, 0);
-        assert_eq!(chip8.i, 0);
-        assert_eq!(chip8.dt, 0);
-        assert_eq!(chip8.st, 0);
+        // Verify initial state
+        assert_eq!(chip8.pc, 0x200);
+        assert_eq!(chip8.sp, 0);
+        assert_eq!(chip8.i, 0);
+        assert_eq!(chip8.dt, 0);
+        assert_eq!(chip8.st, 0);
+    }
+
+    #[test]
+    fn test_new_infallible() {
+        let chip8 = Chip8::new_infallible();
+        assert_eq!(chip8.pc, 0x200);
+        for digit in 0..16 {
+            assert_eq!(
+                chip8.sprite_font_bytes(digit),
+                Chip8::new().unwrap().sprite_font_bytes(digit)
+            );
+        }
+        assert_eq!(
+            chip8.sprite_font_bytes(0xF),
+            Some([0xF0, 0x80, 0xF0, 0x80, 0x80].as_slice())
+        );
+    }
+
+    #[test]
+    fn test_reset_preserves_configuration() {
+        let quirks = Quirks {
+            shift_uses_vy: true,
+            ..Quirks::default()
+        };
+        let mut chip8 = Chip8::builder()
+            .fill_pattern(0xAA)
+            .initial_hires(true)
+            .build()
+            .unwrap();
+        chip8.set_quirks(quirks);
+        chip8.enable_history(4);
+        chip8.enable_pc_history(3);
+
+        chip8.reset().unwrap();
+        assert_eq!(chip8.quirks(), &quirks);
+        assert_eq!(chip8.history_depth, 4);
+        assert_eq!(chip8.pc_history_depth, 3);
+        assert_eq!(chip8.registers, [0xAA; 16]);
+        assert_eq!(chip8.width(), 128);
+    }
+
+    #[test]
+    fn test_with_rom() {
+        let chip8 = Chip8::with_rom(&[0x00, 0xE0, 0x12, 0x00]).unwrap();
+        assert_eq!(
+            chip8.memory.get(ROM_START_ADDRESS..ROM_START_ADDRESS + 4),
+            Some([0x00, 0xE0, 0x12, 0x00].as_slice())
+        );
+        assert_eq!(chip8.pc, 0x200);
+
+        assert!(matches!(
+            Chip8::with_rom(&vec![0; 4096]),
+            Err(Chip8Error::RomTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_new_many() {
+        let machines = Chip8::new_many(100).unwrap();
+        assert_eq!(machines.len(), 100);
+        for chip8 in &machines {
+            assert_eq!(
+                chip8.sprite_font_bytes(0xF),
+                Some([0xF0, 0x80, 0xF0, 0x80, 0x80].as_slice())
+            );
+            assert_eq!(chip8.pc, 0x200);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut chip8 = Chip8::new().unwrap();
+        // Set some state to non-default values
+        let byte = [0xFF];
+        chip8
+            .memory
+            .write_at(&byte, 0x300)
+            .expect("Failed to write memory");
+        chip8.registers[0] = 0xAA;
+        chip8.pc = 0x300;
+        chip8.sp = 5;
+        chip8.i = 0x123;
+        chip8.stack[0] = 0x456;
+        chip8.dt = 10;
+        chip8.st = 20;
+        chip8.framebuffer[0] = 1;
+        chip8.keyboard[0] = 1;
+
+        chip8.reset().unwrap();
+
+        // Verify all fields were reset
+        assert_eq!(chip8.registers, [0; 16]);
+        assert_eq!(chip8.pc, 0x200);
+        assert_eq!(chip8.sp, 0);
+        assert_eq!(chip8.i, 0);
+        assert_eq!(chip8.stack, [0; 16]);
+        assert_eq!(chip8.dt, 0);
+        assert_eq!(chip8.st, 0);
+        assert!(chip8.framebuffer.iter().all(|&p| p == 0));
+        assert_eq!(chip8.keyboard, [0; 16]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert!(chip8.validate().is_ok());
+
+        // Deliberately corrupt the stack pointer
+        chip8.sp = 17;
+        assert!(matches!(chip8.validate(), Err(Chip8Error::SPError(17))));
+        chip8.sp = 16;
+        assert!(chip8.validate().is_ok());
+
+        chip8.pc = memory::RAM_SIZE as u16;
+        assert!(matches!(chip8.validate(), Err(Chip8Error::PCError(_))));
+        chip8.pc = 0x200;
+
+        chip8.i = 0xFFFF;
+        assert!(matches!(
+            chip8.validate(),
+            Err(Chip8Error::IndexError(0xFFFF))
+        ));
+        chip8.i = 0;
+
+        chip8.keyboard[3] = 2;
+        assert!(matches!(chip8.validate(), Err(Chip8Error::InvalidKey(3))));
+    }
+
+    #[test]
+    fn test_sprite_font_bytes() {
+        let chip8 = Chip8::new().unwrap();
+        assert_eq!(
+            chip8.sprite_font_bytes(0x0),
+            Some([0xF0, 0x90, 0x90, 0x90, 0xF0].as_slice())
+        );
+        assert_eq!(
+            chip8.sprite_font_bytes(0xF),
+            Some([0xF0, 0x80, 0xF0, 0x80, 0x80].as_slice())
+        );
+        assert_eq!(chip8.sprite_font_bytes(0x10), None);
+    }
+
+    #[test]
+    fn test_builder_fill_pattern() {
+        let mut chip8 = Chip8::builder().fill_pattern(0xFF).build().unwrap();
+        chip8.load_rom(&[0x12, 0x34]).unwrap();
+
+        assert_eq!(chip8.memory.read_byte(0x300), Some(0xFF));
+        assert_eq!(chip8.memory.read_byte(ROM_START_ADDRESS), Some(0x12));
+        assert_eq!(
+            chip8.sprite_font_bytes(0),
+            Some([0xF0, 0x90, 0x90, 0x90, 0xF0].as_slice())
+        );
+        assert_eq!(chip8.registers, [0xFF; 16]);
+
+        // The pattern is reapplied on reset
+        chip8.reset().unwrap();
+        assert_eq!(chip8.memory.read_byte(ROM_START_ADDRESS), Some(0xFF));
+        assert_eq!(chip8.registers, [0xFF; 16]);
+
+        // Default stays zeroed
+        let chip8 = Chip8::builder().build().unwrap();
+        assert_eq!(chip8.memory.read_byte(0x300), Some(0x00));
+    }
+
+    #[test]
+    fn test_builder_initial_hires() {
+        let mut chip8 = Chip8::builder().initial_hires(true).build().unwrap();
+        assert_eq!(chip8.width(), 128);
+        assert_eq!(chip8.height(), 64);
+        assert_eq!(chip8.framebuffer().len(), 128 * 64);
+
+        // Sprites can be drawn across the whole hires display
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0x80], 0x300).unwrap();
+        chip8.registers[0] = 127;
+        chip8.registers[1] = 63;
+        run_instruction(&mut chip8, 0xD011).unwrap();
+        assert_eq!(chip8.framebuffer()[63 * 128 + 127], 1);
+
+        // The resolution is restored on reset
+        chip8.reset().unwrap();
+        assert_eq!((chip8.width(), chip8.height()), (128, 64));
+
+        let chip8 = Chip8::builder().build().unwrap();
+        assert_eq!((chip8.width(), chip8.height()), (64, 32));
+        assert_eq!(chip8.framebuffer().len(), 64 * 32);
+    }
+
+    #[test]
+    fn test_builder_rng_seed() {
+        let draws = |chip8: &mut Chip8| -> Vec<u8> {
+            (0..16)
+                .map(|_| {
+                    run_instruction(chip8, 0xC0FF).unwrap();
+                    chip8.registers[0]
+                })
+                .collect()
+        };
+
+        let mut a = Chip8::builder().rng_seed(42).build().unwrap();
+        let mut b = Chip8::builder().rng_seed(42).build().unwrap();
+        let sequence = draws(&mut a);
+        assert_eq!(sequence, draws(&mut b));
+
+        // The seed is reapplied on reset
+        a.reset().unwrap();
+        assert_eq!(sequence, draws(&mut a));
+    }
+
+    #[test]
+    fn test_memory() {
+        let chip8 = Chip8::new().unwrap();
+        let ram = chip8.memory();
+        assert_eq!(ram.len(), chip8.memory_len());
+        assert_eq!(ram.len(), 4096);
+
+        let font = &ram[memory::FONT_START_ADDRESS..memory::FONT_START_ADDRESS + 80];
+        assert_eq!(&font[..5], &[0xF0, 0x90, 0x90, 0x90, 0xF0]); // '0'
+        assert_eq!(&font[75..], &[0xF0, 0x80, 0xF0, 0x80, 0x80]); // 'F'
+    }
+
+    #[test]
+    fn test_high_water_mark() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert_eq!(chip8.high_water_mark(), 0);
+
+        chip8.load_rom(&[0x00, 0xE0, 0x00, 0xE0]).unwrap();
+        assert_eq!(chip8.high_water_mark(), 0x203);
+
+        chip8.i = 0x500;
+        run_instruction(&mut chip8, 0xF055).unwrap();
+        assert_eq!(chip8.high_water_mark(), 0x500);
+
+        chip8.reset().unwrap();
+        assert_eq!(chip8.high_water_mark(), 0);
+    }
+
+    #[test]
+    fn test_builder_warn_on_odd_pc() {
+        // JP 0x203; at 0x203: JP 0x203
+        let rom = [0x12, 0x03, 0x00, 0x12, 0x03];
+        let mut chip8 = Chip8::builder().warn_on_odd_pc(true).build().unwrap();
+        chip8.load_rom(&rom).unwrap();
+        for _ in 0..3 {
+            chip8.run().unwrap();
+        }
+        assert_eq!(chip8.odd_pc_warnings(), &[0x203]);
+        assert_eq!(chip8.pc, 0x203);
+
+        chip8.reset().unwrap();
+        assert!(chip8.odd_pc_warnings().is_empty());
+
+        // Disabled by default, without changing execution
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&rom).unwrap();
+        for _ in 0..3 {
+            chip8.run().unwrap();
+        }
+        assert!(chip8.odd_pc_warnings().is_empty());
+        assert_eq!(chip8.pc, 0x203);
+    }
+
+    #[test]
+    fn test_builder_font() {
+        let mut font = [0; 80];
+        // Digit 0 as a filled box
+        font[..5].copy_from_slice(&[0xFF, 0x81, 0x81, 0x81, 0xFF]);
+        let mut chip8 = Chip8::builder().font(&font).build().unwrap();
+        chip8.load_rom(&[0xF0, 0x29, 0xD1, 0x15]).unwrap();
+        chip8.run_until_halt(2).unwrap();
+
+        assert_eq!(chip8.i as usize, memory::FONT_START_ADDRESS);
+        for (row, &byte) in font[..5].iter().enumerate() {
+            for col in 0..8 {
+                let expected = byte >> (7 - col) & 1;
+                assert_eq!(chip8.framebuffer()[row * 64 + col], expected);
+            }
+        }
+
+        // The custom font is reloaded on reset
+        chip8.reset().unwrap();
+        assert_eq!(chip8.sprite_font_bytes(0), Some(&font[..5]));
+    }
+
+    #[test]
+    fn test_builder_protect_font() {
+        let mut chip8 = Chip8::builder().protect_font(true).build().unwrap();
+        chip8.i = memory::FONT_START_ADDRESS as u16;
+        assert!(matches!(
+            run_instruction(&mut chip8, 0xF055),
+            Err(Chip8Error::MemoryError(MemoryError::WriteProtected(_)))
+        ));
+        assert_eq!(
+            chip8.sprite_font_bytes(0),
+            Some([0xF0, 0x90, 0x90, 0x90, 0xF0].as_slice())
+        );
+
+        // The protection survives a reset
+        chip8.reset().unwrap();
+        chip8.i = memory::FONT_START_ADDRESS as u16;
+        assert!(run_instruction(&mut chip8, 0xF033).is_err());
+
+        let mut chip8 = Chip8::builder().build().unwrap();
+        chip8.i = memory::FONT_START_ADDRESS as u16;
+        assert!(run_instruction(&mut chip8, 0xF055).is_ok());
+    }
+
+    #[test]
+    fn test_color_at() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.framebuffer[3 * 64 + 5] = 1;
+
+        assert_eq!(chip8.color_at(5, 3), Some(1));
+        assert_eq!(chip8.color_at(6, 3), Some(0));
+        assert_eq!(chip8.color_at(63, 31), Some(0));
+        assert_eq!(chip8.color_at(64, 0), None);
+        assert_eq!(chip8.color_at(0, 32), None);
+    }
+
+    #[test]
+    fn test_sprite_preview() {
+        let chip8 = Chip8::new().unwrap();
+        let pixels = chip8
+            .sprite_preview(memory::FONT_START_ADDRESS + 8 * 5, 5)
+            .unwrap();
+
+        // The '8' glyph: F0 90 F0 90 F0
+        let expected: Vec<bool> = ["####....", "#..#....", "####....", "#..#....", "####...."]
+            .concat()
+            .chars()
+            .map(|c| c == '#')
+            .collect();
+        assert_eq!(pixels, expected);
+
+        assert_eq!(
+            chip8.sprite_preview(memory::RAM_SIZE - 1, 1).unwrap().len(),
+            8
+        );
+        assert_eq!(chip8.sprite_preview(memory::RAM_SIZE - 1, 2), None);
+        assert_eq!(chip8.sprite_preview(usize::MAX, 2), None);
+    }
+
+    #[test]
+    fn test_display_as_rows() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.framebuffer[2 * 64] = 1;
+        chip8.framebuffer[2 * 64 + 63] = 1;
+        chip8.framebuffer[3 * 64 + 4] = 1;
+
+        let rows = chip8.display_as_rows();
+        assert_eq!(rows.len(), 32);
+        assert_eq!(rows[2], 0x8000_0000_0000_0001);
+        assert_eq!(rows[3], 0x0800_0000_0000_0000);
+        assert_eq!(rows[0], 0);
+
+        // Font sprite "1" drawn at (8, 0)
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0] = 1;
+        chip8.registers[1] = 8;
+        run_instruction(&mut chip8, 0xF029).unwrap();
+        run_instruction(&mut chip8, 0xD125).unwrap();
+        let rows = chip8.display_as_rows();
+        assert_eq!(rows[0], 0x0020_0000_0000_0000);
+        assert_eq!(rows[1], 0x0060_0000_0000_0000);
+        assert_eq!(rows[4], 0x0070_0000_0000_0000);
+    }
+
+    #[test]
+    fn test_take_display_updated() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert!(!chip8.take_display_updated());
+
+        run_instruction(&mut chip8, 0x00E0).unwrap();
+        assert!(chip8.take_display_updated());
+        assert!(!chip8.take_display_updated());
+        assert!(!chip8.is_display_updated());
+    }
+
+    #[test]
+    fn test_framebuffer_mut() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.framebuffer_mut()[3 * 64 + 7] = 1;
+
+        assert_eq!(chip8.framebuffer()[3 * 64 + 7], 1);
+        assert!(!chip8.is_display_updated());
+        assert_eq!(chip8.registers[0xF], 0);
+
+        chip8.mark_display_dirty();
+        assert!(chip8.is_display_updated());
+    }
+
+    #[test]
+    fn test_framebuffer_bits() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0] = 4;
+        chip8.registers[1] = 2;
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0b1011_0001, 0xFF], 0x300).unwrap();
+        tests::run_instruction(&mut chip8, 0xD012).unwrap();
+
+        let bits = chip8.framebuffer_bits();
+        assert_eq!(bits.len(), 64 * 32 / 8);
+        for (index, &pixel) in chip8.framebuffer().iter().enumerate() {
+            let bit = bits[index / 8] >> (7 - index % 8) & 1;
+            assert_eq!(bit, pixel, "pixel {index}");
+        }
+        // The sprite starts at X = 4, straddling two bytes
+        assert_eq!(&bits[2 * 8..2 * 8 + 2], &[0b0000_1011, 0b0001_0000]);
+        assert_eq!(&bits[3 * 8..3 * 8 + 2], &[0b0000_1111, 0b1111_0000]);
+
+        chip8.hires = true;
+        assert_eq!(chip8.framebuffer_bits().len(), 128 * 64 / 8);
+    }
+
+    #[test]
+    fn test_total_pixels_on() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert_eq!(chip8.total_pixels_on(), 0);
+
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0xFF], 0x300).unwrap();
+        tests::run_instruction(&mut chip8, 0xD011).unwrap();
+        assert_eq!(chip8.total_pixels_on(), 8);
+
+        // Drawing the same sprite again erases it
+        tests::run_instruction(&mut chip8, 0xD011).unwrap();
+        assert_eq!(chip8.total_pixels_on(), 0);
+    }
+
+    #[test]
+    fn test_is_display_blank() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert!(chip8.is_display_blank());
+
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0x80], 0x300).unwrap();
+        tests::run_instruction(&mut chip8, 0xD011).unwrap();
+        assert!(!chip8.is_display_blank());
+
+        tests::run_instruction(&mut chip8, 0x00E0).unwrap();
+        assert!(chip8.is_display_blank());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_framebuffer_image() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0] = 10;
+        chip8.registers[1] = 3;
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0x80], 0x300).unwrap();
+        tests::run_instruction(&mut chip8, 0xD011).unwrap();
+
+        let image = chip8.framebuffer_image();
+        assert_eq!(image.dimensions(), (64, 32));
+        assert_eq!(image.get_pixel(10, 3).0, [255]);
+        assert_eq!(image.get_pixel(11, 3).0, [0]);
+
+        chip8.hires = true;
+        assert_eq!(chip8.framebuffer_image().dimensions(), (128, 64));
+    }
+
+    #[test]
+    fn test_clone_framebuffer_into() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.framebuffer_mut()[5] = 1;
+        chip8.framebuffer_mut()[31 * 64 + 63] = 1;
+
+        let mut out = vec![0xAA; 64 * 32];
+        chip8.clone_framebuffer_into(&mut out).unwrap();
+        assert_eq!(out, chip8.framebuffer());
+
+        let mut small = vec![0; 64 * 32 - 1];
+        assert!(matches!(
+            chip8.clone_framebuffer_into(&mut small),
+            Err(Chip8Error::FrameBufferOverflow(2048))
+        ));
+    }
+
+    #[test]
+    fn test_beep_sample_count() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert_eq!(chip8.beep_sample_count(48000), 0);
+
+        chip8.st = 30;
+        assert_eq!(chip8.beep_sample_count(48000), 24000);
+        chip8.st = 1;
+        assert_eq!(chip8.beep_sample_count(44100), 735);
     }
 
     #[test]
-    fn test_reset() {
+    fn test_sound_edge() {
         let mut chip8 = Chip8::new().unwrap();
-        // Set some state to non-default values
-        let byte = [0xFF];
-        chip8
-            .memory
-            .write_at(&byte, 0x300)
-            .expect("Failed to write memory");
-        chip8.registers[0] = 0xAA;
-        chip8.pc = 0x300;
-        chip8.sp = 5;
-        chip8.i = 0x123;
-        chip8.stack[0] = 0x456;
-        chip8.dt = 10;
-        chip8.st = 20;
-        chip8.framebuffer[0] = 1;
-        chip8.keyboard[0] = 1;
+        assert_eq!(chip8.sound_edge(), None);
 
-        chip8.reset().unwrap();
+        chip8.registers[1] = 1;
+        run_instruction(&mut chip8, 0xF118).unwrap();
+        assert_eq!(chip8.sound_edge(), Some(SoundEdge::Start));
+        assert_eq!(chip8.sound_edge(), None);
 
-        // Verify all fields were reset
-        assert_eq!(chip8.registers, [0; 16]);
-        assert_eq!(chip8.pc, 0x200);
-        assert_eq!(chip8.sp, 0);
-        assert_eq!(chip8.i, 0);
-        assert_eq!(chip8.stack, [0; 16]);
-        assert_eq!(chip8.dt, 0);
-        assert_eq!(chip8.st, 0);
-        assert_eq!(chip8.framebuffer, [0; 64 * 32]);
-        assert_eq!(chip8.keyboard, [0; 16]);
+        chip8.tick_timers();
+        assert!(!chip8.should_beep());
+        assert_eq!(chip8.sound_edge(), Some(SoundEdge::Stop));
+
+        // Ticking a silent beeper doesn't produce edges
+        chip8.tick_timers();
+        assert_eq!(chip8.sound_edge(), None);
+    }
+
+    #[test]
+    fn test_registers_snapshot() {
+        let mut chip8 = Chip8::new().unwrap();
+        run_instruction(&mut chip8, 0x6A42).unwrap(); // LD VA, 0x42
+        let snapshot = chip8.registers_snapshot();
+        assert_eq!(snapshot, chip8.registers);
+        assert_eq!(snapshot[0xA], 0x42);
+
+        run_instruction(&mut chip8, 0x7A01).unwrap(); // ADD VA, 0x01
+        assert_eq!(snapshot[0xA], 0x42);
+        assert_eq!(chip8.registers_snapshot()[0xA], 0x43);
     }
 
     #[test]
@@ -616,8 +2521,8 @@ mod tests {
         for tick in 1..=60 {
             chip8.tick_timers();
 
-            let expected_dt = if tick <= 60 { 60 - tick } else { 0 };
-            let expected_st = if tick <= 30 { 30 - tick } else { 0 };
+            let expected_dt = 60u8.saturating_sub(tick);
+            let expected_st = 30u8.saturating_sub(tick);
 
             assert_eq!(chip8.delay_timer(), expected_dt);
             assert_eq!(chip8.sound_timer(), expected_st);
@@ -688,11 +2593,423 @@ mod tests {
         assert_eq!(chip8.registers[2], 32);
     }
 
+    #[test]
+    fn test_tick_and_run() {
+        let mut chip8 = Chip8::new().unwrap();
+        // LD V0, 1; ADD V0, 1; ADD V0, 1; ADD V0, 1
+        let rom = [0x60, 0x01, 0x70, 0x01, 0x70, 0x01, 0x70, 0x01];
+        chip8.load_rom(&rom).unwrap();
+        chip8.dt = 10;
+
+        chip8.tick_and_run(3).unwrap();
+
+        assert_eq!(chip8.pc, 0x206);
+        assert_eq!(chip8.registers[0], 3);
+        assert_eq!(chip8.delay_timer(), 9);
+    }
+
+    #[test]
+    fn test_tick_and_run_stops_on_key_wait() {
+        let mut chip8 = Chip8::new().unwrap();
+        // LD V1, K; LD V0, 1
+        let rom = [0xF1, 0x0A, 0x60, 0x01];
+        chip8.load_rom(&rom).unwrap();
+        chip8.dt = 10;
+
+        chip8.tick_and_run(10).unwrap();
+
+        // Still blocked on FX0A, but the timers have ticked
+        assert_eq!(chip8.pc, 0x200);
+        assert_eq!(chip8.registers[0], 0);
+        assert_eq!(chip8.delay_timer(), 9);
+
+        chip8.key_press(0x7);
+        chip8.tick_and_run(2).unwrap();
+        assert_eq!(chip8.registers[1], 0x7);
+        assert_eq!(chip8.registers[0], 1);
+        assert_eq!(chip8.delay_timer(), 8);
+    }
+
+    #[test]
+    fn test_run_frames() {
+        let mut chip8 = Chip8::new().unwrap();
+        // LD I, 0x050; DRW V0, V1, 5; ADD V0, 1; JP 0x202
+        let rom = [0xA0, 0x50, 0xD0, 0x15, 0x70, 0x01, 0x12, 0x02];
+        chip8.load_rom(&rom).unwrap();
+
+        let mut frames = Vec::new();
+        chip8
+            .run_frames(4, 3, |framebuffer| frames.push(framebuffer.to_vec()))
+            .unwrap();
+
+        assert_eq!(frames.len(), 4);
+        assert!(frames.iter().all(|frame| frame.len() == 64 * 32));
+        assert!(frames[0].contains(&1));
+        assert_ne!(frames[0], frames[1]);
+    }
+
+    #[test]
+    fn test_run_skip_invalid() {
+        let mut chip8 = Chip8::new().unwrap();
+        // LD V0, 1; <invalid>; ADD V0, 1
+        let rom = [0x60, 0x01, 0xFF, 0xFF, 0x70, 0x01];
+        chip8.load_rom(&rom).unwrap();
+
+        assert!(chip8.run_skip_invalid().unwrap());
+        assert!(!chip8.run_skip_invalid().unwrap());
+        assert!(chip8.run_skip_invalid().unwrap());
+
+        assert_eq!(chip8.registers[0], 2);
+        assert_eq!(chip8.pc, 0x206);
+    }
+
+    #[test]
+    fn test_run_skip_invalid_propagates_other_errors() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.pc = (memory::RAM_SIZE - 1) as u16;
+        assert!(matches!(
+            chip8.run_skip_invalid(),
+            Err(Chip8Error::PCError(_))
+        ));
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut chip8 = Chip8::new().unwrap();
+        // LD V0, 1; LD V1, 2; CALL 0x300
+        let rom = [0x60, 0x01, 0x61, 0x02, 0x23, 0x00];
+        chip8.load_rom(&rom).unwrap();
+        chip8.enable_history(8);
+
+        chip8.run().unwrap();
+        let after_first = chip8.snapshot();
+        chip8.run().unwrap();
+        chip8.run().unwrap();
+        assert_eq!(chip8.pc, 0x300);
+        assert_eq!(chip8.sp, 1);
+
+        assert!(chip8.step_back());
+        assert!(chip8.step_back());
+        assert_eq!(chip8.snapshot(), after_first);
+
+        assert!(chip8.step_back());
+        assert_eq!(chip8.pc, 0x200);
+        assert!(!chip8.step_back());
+    }
+
+    #[test]
+    fn test_step_back_restores_rng() {
+        // RND V0, 0xFF; RND V0, 0xFF
+        let mut chip8 = Chip8::builder().rng_seed(3).build().unwrap();
+        chip8.load_rom(&[0xC0, 0xFF, 0xC0, 0xFF]).unwrap();
+        chip8.enable_history(8);
+
+        chip8.run().unwrap();
+        chip8.run().unwrap();
+        let second = chip8.registers[0];
+
+        assert!(chip8.step_back());
+        assert_eq!(chip8.random_draws(), 1);
+        chip8.run().unwrap();
+        assert_eq!(chip8.registers[0], second);
+        assert_eq!(chip8.random_draws(), 2);
+    }
+
+    #[test]
+    fn test_step_back_skips_failed_fetch() {
+        let mut chip8 = Chip8::with_rom(&[0x60, 0x01]).unwrap(); // LD V0, 0x01
+        chip8.enable_history(8);
+        chip8.run().unwrap();
+
+        chip8.pc = memory::RAM_SIZE as u16;
+        assert!(matches!(chip8.run(), Err(Chip8Error::PCError(_))));
+
+        // The failed cycle left no entry, so the LD is undone
+        assert!(chip8.step_back());
+        assert_eq!(chip8.pc, 0x200);
+        assert_eq!(chip8.registers[0], 0);
+        assert!(!chip8.step_back());
+    }
+
+    #[test]
+    fn test_step_back_keeps_queued_key_events() {
+        // LD V0, 0x05; SKP V0; LD V1, 0x01; LD V2, 0x01
+        let mut chip8 = Chip8::with_rom(&[0x60, 0x05, 0xE0, 0x9E, 0x61, 0x01, 0x62, 0x01]).unwrap();
+        chip8.enable_history(8);
+        chip8.queue_key_event(1, 0x5, true);
+
+        chip8.run().unwrap();
+        chip8.run().unwrap();
+        assert_eq!(chip8.pc, 0x206);
+
+        // Replaying the SKP still sees the recorded press
+        assert!(chip8.step_back());
+        assert_eq!(chip8.keyboard[0x5], 1);
+        chip8.run().unwrap();
+        assert_eq!(chip8.pc, 0x206);
+    }
+
+    #[test]
+    fn test_step_back_depth_limit() {
+        let mut chip8 = Chip8::new().unwrap();
+        let rom = [0x70, 0x01, 0x70, 0x01, 0x70, 0x01, 0x70, 0x01];
+        chip8.load_rom(&rom).unwrap();
+
+        // Disabled by default
+        chip8.run().unwrap();
+        assert!(!chip8.step_back());
+
+        chip8.enable_history(2);
+        for _ in 0..3 {
+            chip8.run().unwrap();
+        }
+        assert!(chip8.step_back());
+        assert!(chip8.step_back());
+        assert!(!chip8.step_back());
+        assert_eq!(chip8.registers[0], 2);
+    }
+
+    #[test]
+    fn test_set_keyboard_state() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.key_press(0x3);
+
+        chip8.set_keyboard_state(0b1000_0000_0000_0101);
+        assert_eq!(chip8.keyboard[0x0], 1);
+        assert_eq!(chip8.keyboard[0x2], 1);
+        assert_eq!(chip8.keyboard[0x3], 0);
+        assert_eq!(chip8.keyboard[0xF], 1);
+        assert_eq!(chip8.keyboard_state(), 0b1000_0000_0000_0101);
+
+        chip8.set_keyboard_state(0);
+        assert_eq!(chip8.keyboard, [0; 16]);
+    }
+
+    #[test]
+    fn test_error_exit_code() {
+        assert_eq!(Chip8Error::InvalidOpCode("0000".into()).exit_code(), 2);
+        assert_eq!(
+            Chip8Error::MemoryError(MemoryError::OutOfMemory).exit_code(),
+            3
+        );
+        assert_eq!(
+            Chip8Error::RomTooLarge {
+                size: 4000,
+                capacity: 3584
+            }
+            .exit_code(),
+            11
+        );
+        assert_eq!(Chip8Error::InvalidStateDiff("truncated").exit_code(), 13);
+    }
+
+    #[test]
+    fn test_error_is_recoverable() {
+        assert!(Chip8Error::InvalidOpCode("5121".into()).is_recoverable());
+        assert!(Chip8Error::InvalidKey(0x10).is_recoverable());
+
+        assert!(!Chip8Error::MemoryError(MemoryError::OutOfMemory).is_recoverable());
+        assert!(!Chip8Error::PCError(0xFFF).is_recoverable());
+        assert!(!Chip8Error::SPError(16).is_recoverable());
+        assert!(!Chip8Error::SPOverflow(0).is_recoverable());
+        assert!(!Chip8Error::IndexError(0x1010).is_recoverable());
+        assert!(
+            !Chip8Error::RomTooLarge {
+                size: 4000,
+                capacity: 3584
+            }
+            .is_recoverable()
+        );
+
+        // A skipped invalid opcode leaves the machine ready for the next one
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&[0x51, 0x21, 0x60, 0x42]).unwrap();
+        let error = chip8.run().unwrap_err();
+        assert!(error.is_recoverable());
+        chip8.run().unwrap();
+        assert_eq!(chip8.registers[0], 0x42);
+    }
+
+    #[test]
+    fn test_press_release_key() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.press(Key::KeyA);
+        assert!(chip8.key_state(0xA));
+        assert_eq!(chip8.keyboard[0xA], 1);
+        assert_eq!(chip8.keyboard_state(), 1 << 0xA);
+
+        chip8.release(Key::KeyA);
+        assert!(!chip8.key_state(0xA));
+    }
+
+    #[test]
+    fn test_key_edges() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.key_press(0x5);
+        assert!(chip8.key_just_pressed(0x5));
+        assert!(!chip8.key_just_pressed(0x6));
+
+        // Held for a second frame
+        chip8.tick_timers();
+        assert!(chip8.key_state(0x5));
+        assert!(!chip8.key_just_pressed(0x5));
+
+        chip8.key_release(0x5);
+        assert!(chip8.key_just_released(0x5));
+        chip8.tick_timers();
+        assert!(!chip8.key_just_released(0x5));
+
+        assert!(!chip8.key_just_pressed(16));
+        assert!(!chip8.key_just_released(16));
+    }
+
+    #[test]
+    fn test_set_keyboard() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.key_press(0x3);
+
+        let mut keys = [false; 16];
+        keys[0x0] = true;
+        keys[0xA] = true;
+        chip8.set_keyboard(keys);
+
+        for key in 0..16 {
+            assert_eq!(chip8.key_state(key), key == 0x0 || key == 0xA, "key {key}");
+        }
+        assert!(!chip8.key_state(16));
+        assert_eq!(chip8.keyboard_state(), (1 << 0x0) | (1 << 0xA));
+    }
+
+    #[test]
+    fn test_queue_key_event() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&[0x12, 0x00]).unwrap(); // JP 0x200
+        chip8.queue_key_event(5, 0xA, true);
+
+        for cycle in 0..10 {
+            assert_eq!(chip8.cycle_count(), cycle);
+            chip8.run().unwrap();
+            assert_eq!(chip8.keyboard[0xA] == 1, cycle >= 5, "cycle {cycle}");
+        }
+        assert_eq!(chip8.cycle_count(), 10);
+    }
+
+    #[test]
+    fn test_queue_key_event_order() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&[0x12, 0x00]).unwrap();
+        chip8.queue_key_event(3, 0x1, false);
+        chip8.queue_key_event(1, 0x1, true);
+
+        chip8.run().unwrap();
+        assert_eq!(chip8.keyboard[0x1], 0);
+        chip8.run().unwrap();
+        assert_eq!(chip8.keyboard[0x1], 1);
+        chip8.run().unwrap();
+        assert_eq!(chip8.keyboard[0x1], 1);
+        chip8.run().unwrap();
+        assert_eq!(chip8.keyboard[0x1], 0);
+    }
+
+    #[test]
+    fn test_step_waits_for_key() {
+        // LD V0, K; LD V1, 0x01
+        let mut chip8 = Chip8::with_rom(&[0xF0, 0x0A, 0x61, 0x01]).unwrap();
+        assert_eq!(chip8.step().unwrap(), RunStatus::WaitingForKey);
+        assert_eq!(chip8.cycle_count(), 1);
+
+        // The instruction isn't executed again while no key is pressed
+        for _ in 0..5 {
+            assert_eq!(chip8.step().unwrap(), RunStatus::WaitingForKey);
+        }
+        assert_eq!(chip8.cycle_count(), 1);
+        assert_eq!(chip8.pc, 0x200);
+
+        chip8.key_press(0x7);
+        assert_eq!(chip8.step().unwrap(), RunStatus::Running);
+        assert_eq!(chip8.registers[0], 0x7);
+        assert_eq!(chip8.cycle_count(), 2);
+        assert_eq!(chip8.step().unwrap(), RunStatus::Running);
+        assert_eq!(chip8.registers[1], 0x1);
+
+        let mut chip8 = Chip8::with_rom(&[0x00, 0xFD]).unwrap();
+        assert_eq!(chip8.step().unwrap(), RunStatus::Halted);
+    }
+
+    #[test]
+    fn test_step_applies_queued_key_events() {
+        // LD V0, K; LD V1, 0x01
+        let mut chip8 = Chip8::with_rom(&[0xF0, 0x0A, 0x61, 0x01]).unwrap();
+        assert_eq!(chip8.step().unwrap(), RunStatus::WaitingForKey);
+        assert_eq!(chip8.step().unwrap(), RunStatus::WaitingForKey);
+
+        // The cycle count doesn't advance while waiting, so queue at the current cycle
+        chip8.queue_key_event(chip8.cycle_count(), 0x7, true);
+        assert_eq!(chip8.step().unwrap(), RunStatus::Running);
+        assert_eq!(chip8.registers[0], 0x7);
+        assert_eq!(chip8.step().unwrap(), RunStatus::Running);
+        assert_eq!(chip8.registers[1], 0x1);
+    }
+
+    #[test]
+    fn test_run_until_halt() {
+        // LD V0, 0x01; LD V1, 0x02; EXIT; LD V2, 0x03
+        let mut chip8 = Chip8::with_rom(&[0x60, 0x01, 0x61, 0x02, 0x00, 0xFD, 0x62, 0x03]).unwrap();
+        assert_eq!(chip8.run_until_halt(100).unwrap(), 3);
+        assert!(chip8.is_halted());
+        assert_eq!(chip8.registers[2], 0);
+
+        // LD V0, 0x01; JP 0x202
+        let mut chip8 = Chip8::with_rom(&[0x60, 0x01, 0x12, 0x02]).unwrap();
+        assert_eq!(chip8.run_until_halt(100).unwrap(), 1);
+        assert!(!chip8.is_halted());
+        assert_eq!(chip8.pc, 0x202);
+
+        // JP 0x202; JP 0x200
+        let mut chip8 = Chip8::with_rom(&[0x12, 0x02, 0x12, 0x00]).unwrap();
+        assert_eq!(chip8.run_until_halt(10).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_run_until_halt_within() {
+        // ADD V0, 0x01; JP 0x200
+        let mut chip8 = Chip8::with_rom(&[0x70, 0x01, 0x12, 0x00]).unwrap();
+        let start = Instant::now();
+        let cycles = chip8
+            .run_until_halt_within(Duration::from_millis(20))
+            .unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(cycles >= DEADLINE_CHECK_INTERVAL);
+        assert_eq!(cycles % DEADLINE_CHECK_INTERVAL, 0);
+
+        // A terminating program returns as soon as it halts
+        let mut chip8 = Chip8::with_rom(&[0x60, 0x01, 0x00, 0xFD]).unwrap();
+        assert_eq!(
+            chip8
+                .run_until_halt_within(Duration::from_secs(60))
+                .unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_fetch_at() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&[0x00, 0xE0, 0xA2, 0x2A]).unwrap();
+
+        assert_eq!(chip8.fetch_at(0x202).unwrap(), Instruction::new(0xA22A));
+        assert_eq!(chip8.pc, 0x200);
+        assert!(matches!(
+            chip8.fetch_at(0x0FFF),
+            Err(Chip8Error::PCError(0x0FFF))
+        ));
+    }
+
     #[test]
     fn test_load_rom() {
         let mut chip8 = Chip8::new().unwrap();
         let rom_data = vec![0x1, 0x2, 0x3, 0x4];
-        chip8.load_rom(&rom_data).unwrap();
+        assert_eq!(chip8.load_rom(&rom_data).unwrap(), 4);
 
         let memory_slice = chip8
             .memory
@@ -709,7 +3026,61 @@ mod tests {
 
         assert!(matches!(
             chip8.load_rom(&rom_data),
-            Err(Chip8Error::MemoryError(_))
+            Err(Chip8Error::RomTooLarge {
+                size: 3585,
+                capacity: 3584
+            })
+        ));
+    }
+
+    #[test]
+    fn test_program_changed_since() {
+        // LD V0, 0xAB; LD I, 0x206; LD [I], V0
+        let mut chip8 = Chip8::with_rom(&[0x60, 0xAB, 0xA2, 0x06, 0xF0, 0x55, 0x00, 0x00]).unwrap();
+        let baseline = chip8.program_checksum();
+
+        chip8.run().unwrap();
+        chip8.run().unwrap();
+        assert!(!chip8.program_changed_since(baseline));
+
+        chip8.run().unwrap();
+        assert!(chip8.program_changed_since(baseline));
+    }
+
+    #[test]
+    fn test_load_rom_slice() {
+        let mut chip8 = Chip8::new().unwrap();
+        // 4-byte header, 4-byte ROM, 2-byte trailer
+        let data = [b'C', b'H', b'8', 0x01, 0x60, 0x42, 0x12, 0x02, 0xEE, 0xEE];
+        chip8.load_rom_slice(&data, 4, 4).unwrap();
+        assert_eq!(
+            chip8.memory()[0x200..0x206],
+            [0x60, 0x42, 0x12, 0x02, 0x00, 0x00]
+        );
+
+        assert!(matches!(
+            chip8.load_rom_slice(&data, 8, 3),
+            Err(Chip8Error::InvalidRomSlice {
+                offset: 8,
+                len: 3,
+                size: 10
+            })
+        ));
+        assert!(chip8.load_rom_slice(&data, usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_readonly_rom_region() {
+        let mut chip8 = Chip8::new().unwrap();
+        // LD I, 0x202; LD [I], V0
+        let rom = [0xA2, 0x02, 0xF0, 0x55];
+        let size = chip8.load_rom(&rom).unwrap();
+        chip8.set_readonly_range(ROM_START_ADDRESS..ROM_START_ADDRESS + size);
+
+        chip8.run().unwrap();
+        assert!(matches!(
+            chip8.run(),
+            Err(Chip8Error::MemoryError(MemoryError::WriteProtected(0x202)))
         ));
     }
 
@@ -750,22 +3121,172 @@ mod tests {
         // PC should not advance on failure
         assert_eq!(chip8.pc, initial_pc);
     }
-}
 
-/// Returns the width of the framebuffer.
-///
-/// # Returns
-///
-/// The width of the framebuffer. 64 pixels.
-pub fn framebuffer_width() -> usize {
-    FRAMEBUFFER_WIDTH
-}
+    #[test]
+    fn test_run_traced() {
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&[0x8A, 0xB4, 0x00, 0xFD]).unwrap();
+
+        let instruction = chip8.run_traced().unwrap();
+        assert_eq!(instruction, Instruction::new(0x8AB4));
+        assert_eq!((instruction.instruction(), instruction.x()), (0x8, 0xA));
+        assert_eq!((instruction.y(), instruction.n()), (0xB, 0x4));
+        assert_eq!(
+            instruction.decode().unwrap(),
+            DecodedOp::AddVxVy { x: 0xA, y: 0xB }
+        );
+        assert_eq!(chip8.pc, 0x202);
+
+        assert_eq!(chip8.run_traced().unwrap(), Instruction::new(0x00FD));
+        assert!(chip8.is_halted());
+        // A halted machine executes nothing
+        assert_eq!(chip8.run_traced().unwrap(), Instruction::new(0x00FD));
+        assert_eq!(chip8.cycle_count(), 2);
+    }
 
-/// Returns the height of the framebuffer.
-///
-/// # Returns
-///
-/// The height of the framebuffer. 32 pixels.
-pub fn framebuffer_height() -> usize {
-    FRAMEBUFFER_HEIGHT
+    #[test]
+    fn test_render_scaled_rgba() {
+        const FG: [u8; 4] = [0xFF, 0xC0, 0x00, 0xFF];
+        const BG: [u8; 4] = [0x10, 0x10, 0x10, 0xFF];
+
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.registers[0] = 3;
+        chip8.registers[1] = 1;
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0b1010_0000], 0x300).unwrap();
+        tests::run_instruction(&mut chip8, 0xD011).unwrap();
+
+        let mut out = vec![0; 128 * 64 * 4];
+        chip8.render_scaled_rgba(2, FG, BG, &mut out).unwrap();
+        for y in 0..64 {
+            for x in 0..128 {
+                let expected = if chip8.framebuffer()[y / 2 * 64 + x / 2] != 0 {
+                    FG
+                } else {
+                    BG
+                };
+                let offset = (y * 128 + x) * 4;
+                assert_eq!(out[offset..offset + 4], expected, "({x}, {y})");
+            }
+        }
+        // Source pixel (3, 1) covers the 2x2 block at (6, 2)
+        for (x, y) in [(6, 2), (7, 2), (6, 3), (7, 3)] {
+            let offset = (y * 128 + x) * 4;
+            assert_eq!(out[offset..offset + 4], FG);
+        }
+
+        assert!(matches!(
+            chip8.render_scaled_rgba(3, FG, BG, &mut out),
+            Err(Chip8Error::FrameBufferOverflow(len)) if len == 192 * 96 * 4
+        ));
+    }
+
+    #[test]
+    fn test_pc_history() {
+        let mut chip8 = Chip8::new().unwrap();
+        // 0x200: LD V0, 1; CALL 0x208; JP 0x200; (pad); 0x208: ADD V0, 1; RET
+        chip8
+            .load_rom(&[
+                0x60, 0x01, 0x22, 0x08, 0x12, 0x00, 0x00, 0x00, 0x70, 0x01, 0x00, 0xEE,
+            ])
+            .unwrap();
+        chip8.run().unwrap();
+        assert!(chip8.pc_history().is_empty());
+
+        chip8.enable_pc_history(4);
+        for _ in 0..4 {
+            chip8.run().unwrap();
+        }
+        assert_eq!(chip8.pc_history(), &[0x202, 0x208, 0x20A, 0x204]);
+
+        // Only the most recent addresses are kept
+        for _ in 0..7 {
+            chip8.run().unwrap();
+        }
+        assert_eq!(chip8.pc_history(), &[0x20A, 0x204, 0x200, 0x202]);
+
+        // A failing fetch is still recorded
+        chip8.pc = 0xFFF;
+        assert!(chip8.run().is_err());
+        assert_eq!(chip8.pc_history().last(), Some(&0xFFF));
+
+        chip8.reset().unwrap();
+        assert!(chip8.pc_history().is_empty());
+        chip8.load_rom(&[0x12, 0x00]).unwrap();
+        chip8.enable_pc_history(0);
+        chip8.run().unwrap();
+        assert!(chip8.pc_history().is_empty());
+    }
+
+    #[test]
+    fn test_remaining_rom_space() {
+        let mut chip8 = Chip8::new().unwrap();
+        assert_eq!(chip8.remaining_rom_space(), 3584);
+
+        let rom = vec![0x12; chip8.remaining_rom_space()];
+        assert_eq!(chip8.load_rom(&rom).unwrap(), 3584);
+        assert!(matches!(
+            chip8.load_rom(&[0x12; 3585]),
+            Err(Chip8Error::RomTooLarge {
+                size: 3585,
+                capacity: 3584
+            })
+        ));
+    }
+
+    #[test]
+    fn test_tick_and_run_display_wait() {
+        // DRW V0, V1, 1; DRW V0, V1, 1; LD V2, 0x01; JP 0x206
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.set_quirks(Quirks {
+            display_wait: true,
+            ..Quirks::default()
+        });
+        chip8
+            .load_rom(&[0xD0, 0x11, 0xD0, 0x11, 0x62, 0x01, 0x12, 0x06])
+            .unwrap();
+
+        for expected in [1, 2, 12] {
+            chip8.tick_and_run(10).unwrap();
+            assert_eq!(chip8.cycle_count(), expected);
+        }
+    }
+
+    #[test]
+    fn test_run_frame_display_wait() {
+        // DRW V0, V1, 1; DRW V0, V1, 1; LD V2, 0x01; JP 0x206
+        let rom = [0xD0, 0x11, 0xD0, 0x11, 0x62, 0x01, 0x12, 0x06];
+
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.set_quirks(Quirks {
+            display_wait: true,
+            ..Quirks::default()
+        });
+        chip8.load_rom(&rom).unwrap();
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0x80], 0x300).unwrap();
+
+        // Only the first draw runs, the rest of the frame is idle
+        chip8.run_frame(10).unwrap();
+        assert_eq!(chip8.cycle_count(), 1);
+        assert_eq!(chip8.total_pixels_on(), 1);
+
+        chip8.run_frame(10).unwrap();
+        assert_eq!(chip8.cycle_count(), 2);
+        assert!(chip8.is_display_blank());
+
+        // Without draws, the frame runs all its cycles
+        chip8.run_frame(10).unwrap();
+        assert_eq!(chip8.cycle_count(), 12);
+        assert_eq!(chip8.registers[2], 0x01);
+
+        // Without the quirk, both draws run in one frame
+        let mut chip8 = Chip8::new().unwrap();
+        chip8.load_rom(&rom).unwrap();
+        chip8.i = 0x300;
+        chip8.memory.write_at(&[0x80], 0x300).unwrap();
+        chip8.run_frame(10).unwrap();
+        assert_eq!(chip8.cycle_count(), 10);
+        assert!(chip8.is_display_blank());
+    }
 }
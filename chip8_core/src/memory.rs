@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 /// Standard CHIP-8 font set (hex digits 0-F)
 /// Each digit is 5 bytes representing an 8x5 pixel sprite
 const FONT_SET: [u8; 80] = [
@@ -22,6 +24,9 @@ const FONT_SET: [u8; 80] = [
 /// Memory address where font sprites are loaded
 pub const FONT_START_ADDRESS: usize = 0x50;
 
+/// Number of bytes in a single font sprite
+pub const FONT_SPRITE_SIZE: usize = 5;
+
 pub(super) const RAM_SIZE: usize = 4096;
 
 /// Represents the CHIP-8's 4KB of RAM.
@@ -30,8 +35,11 @@ pub(super) const RAM_SIZE: usize = 4096;
 /// - `0x000-0x1FF`: Chip-8 interpreter (contains font set in emu)
 /// - `0x050-0x0A0`: Used for the built in 4x5 pixel font set (0-F). See [FONT_SET].
 /// - `0x200-0xFFF`: Program ROM and work RAM. See `crate::consts::ROM_START_ADDRESS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Memory {
     ram: [u8; RAM_SIZE],
+    readonly_ranges: Vec<Range<usize>>,
+    high_water_mark: usize,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -40,6 +48,8 @@ pub enum MemoryError {
     Unrecoverable(String),
     #[error("out of memory")]
     OutOfMemory,
+    #[error("write to protected address {0:#05X}")]
+    WriteProtected(usize),
 }
 
 impl Memory {
@@ -53,8 +63,26 @@ impl Memory {
     /// Returns `MemoryError` if the font set cannot be loaded, though this is unlikely
     /// under normal circumstances as the font set and its location are fixed. See [MemoryError].
     pub fn try_new() -> Result<Self, MemoryError> {
-        let mut mem = Memory { ram: [0; RAM_SIZE] };
+        Self::try_new_filled(0)
+    }
+
+    /// Creates a new `Memory` instance with every byte set to `pattern`.
+    ///
+    /// The font set is loaded over the pattern, so only memory outside the font
+    /// region holds the fill value. See [`Memory::try_new()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `MemoryError` if the font set cannot be loaded. See [MemoryError].
+    pub fn try_new_filled(pattern: u8) -> Result<Self, MemoryError> {
+        let mut mem = Memory {
+            ram: [pattern; RAM_SIZE],
+            readonly_ranges: Vec::new(),
+            high_water_mark: 0,
+        };
         mem.load_font()?;
+        // The font is part of the interpreter, not of the program's memory usage
+        mem.high_water_mark = 0;
         Ok(mem)
     }
 
@@ -72,14 +100,63 @@ impl Memory {
         self.ram.get(address).copied()
     }
 
+    /// Reads a big-endian 16-bit word from a given memory address.
+    ///
+    /// The byte at `address` is the most significant byte and the byte at
+    /// `address + 1` the least significant one. This is the byte order CHIP-8
+    /// uses for opcodes.
+    ///
+    /// # Parameters
+    ///
+    /// - `address`: The memory address of the first byte.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(u16)` with the value if both bytes are in bounds, or `None` otherwise.
     pub fn read_word(&self, address: usize) -> Option<u16> {
         self.ram
             .get(address..address + 2)
-            .map(|bytes| ((bytes[0] as u16) << 8) | bytes[1] as u16)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Reads a little-endian 16-bit word from a given memory address.
+    ///
+    /// The byte at `address` is the least significant byte and the byte at
+    /// `address + 1` the most significant one. CHIP-8 itself never uses this
+    /// order; it is provided for variants that store data little-endian.
+    ///
+    /// # Parameters
+    ///
+    /// - `address`: The memory address of the first byte.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(u16)` with the value if both bytes are in bounds, or `None` otherwise.
+    pub fn read_word_le(&self, address: usize) -> Option<u16> {
+        self.ram
+            .get(address..address + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Writes a single byte to a given memory address.
+    ///
+    /// # Parameters
+    ///
+    /// - `address`: The memory address to write to.
+    /// - `value`: The byte to write.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MemoryError::OutOfMemory` if the address is out of bounds, or
+    /// `MemoryError::WriteProtected` if the address is in a read-only range.
+    pub fn write_byte(&mut self, address: usize, value: u8) -> Result<(), MemoryError> {
+        self.write_at(&[value], address)
     }
 
     /// Writes a slice of bytes to memory starting at a given offset.
     ///
+    /// The write is all-or-nothing: on error, memory is left unchanged.
+    ///
     /// # Parameters
     ///
     /// - `buf`: The slice of bytes to write.
@@ -88,15 +165,131 @@ impl Memory {
     /// # Errors
     ///
     /// Returns `MemoryError::OutOfMemory` if writing the buffer would exceed the
-    /// available RAM size ([RAM_SIZE]).
+    /// available RAM size ([RAM_SIZE]), or `MemoryError::WriteProtected` with the
+    /// first protected address if the buffer overlaps a read-only range.
     pub fn write_at(&mut self, buf: &[u8], offset: usize) -> Result<(), MemoryError> {
         if offset + buf.len() > RAM_SIZE {
             return Err(MemoryError::OutOfMemory);
         }
+        if let Some(address) = self.first_protected(offset..offset + buf.len()) {
+            return Err(MemoryError::WriteProtected(address));
+        }
         self.ram[offset..offset + buf.len()].copy_from_slice(buf);
+        if let Some(last) = (offset + buf.len()).checked_sub(1) {
+            self.high_water_mark = self.high_water_mark.max(last);
+        }
         Ok(())
     }
 
+    /// Returns the highest address written since the memory was created.
+    ///
+    /// Writes made while loading the font set are not counted, so this
+    /// approximates how much of the RAM a program touches. Returns `0` if
+    /// nothing has been written yet.
+    pub fn high_water_mark(&self) -> usize {
+        self.high_water_mark
+    }
+
+    /// Overwrites memory directly, ignoring read-only ranges.
+    ///
+    /// Used to restore machine state; the high water mark is not updated.
+    pub(crate) fn patch(&mut self, offset: usize, bytes: &[u8]) {
+        self.ram[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Overrides the high water mark, e.g. when restoring machine state.
+    pub(crate) fn set_high_water_mark(&mut self, mark: usize) {
+        self.high_water_mark = mark;
+    }
+
+    /// Marks a range of memory as read-only.
+    ///
+    /// Subsequent writes overlapping the range fail with
+    /// `MemoryError::WriteProtected`. Multiple ranges can be protected; they
+    /// stay protected until [`Memory::clear_readonly_ranges()`] is called.
+    ///
+    /// # Parameters
+    ///
+    /// - `range`: The range of addresses to protect.
+    pub fn set_readonly_range(&mut self, range: Range<usize>) {
+        self.readonly_ranges.push(range);
+    }
+
+    /// Replaces the font set with custom digit sprites.
+    ///
+    /// The 16 sprites of 5 bytes each are written to [FONT_START_ADDRESS] in
+    /// place of [FONT_SET], so `FX29` points into them like into the built-in
+    /// font. Like the built-in font, this is interpreter data: the write
+    /// ignores read-only ranges and doesn't count towards the high water mark.
+    ///
+    /// # Parameters
+    ///
+    /// - `font`: The sprites for the hex digits 0-F, in order.
+    pub fn set_font(&mut self, font: &[u8; 80]) {
+        self.patch(FONT_START_ADDRESS, font);
+    }
+
+    /// Marks the built-in font set as read-only.
+    ///
+    /// Buggy ROMs sometimes point I into the font region and write to it,
+    /// corrupting the digit sprites used later by `FX29`. See
+    /// [`Memory::set_readonly_range()`].
+    pub fn protect_font(&mut self) {
+        self.set_readonly_range(FONT_START_ADDRESS..FONT_START_ADDRESS + FONT_SET.len());
+    }
+
+    /// Removes all read-only ranges, making the whole memory writable again.
+    pub fn clear_readonly_ranges(&mut self) {
+        self.readonly_ranges.clear();
+    }
+
+    /// Returns the lowest protected address within `range`, if any.
+    fn first_protected(&self, range: Range<usize>) -> Option<usize> {
+        self.readonly_ranges
+            .iter()
+            .filter(|protected| protected.start < range.end && range.start < protected.end)
+            .map(|protected| protected.start.max(range.start))
+            .min()
+    }
+
+    /// Computes a checksum of a range of memory.
+    ///
+    /// The checksum is the 32-bit FNV-1a hash of the bytes, which is cheap
+    /// and stable across builds, so it can be stored and compared later to
+    /// detect changes, e.g. self-modifying code. The range is clamped to the
+    /// RAM size.
+    ///
+    /// # Arguments
+    ///
+    /// * `range`: The range of addresses to checksum.
+    pub fn checksum_range(&self, range: Range<usize>) -> u32 {
+        let end = range.end.min(self.ram.len());
+        let start = range.start.min(end);
+        self.ram[start..end]
+            .iter()
+            .fold(0x811C_9DC5, |hash, &byte| {
+                (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+            })
+    }
+
+    /// Returns the size of the RAM in bytes.
+    pub fn size(&self) -> usize {
+        self.ram.len()
+    }
+
+    /// Returns the number of bytes from `offset` to the end of the RAM.
+    ///
+    /// This is how much data can be written at `offset`, e.g. to check
+    /// whether a ROM fits before loading it.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset`: The address to start counting from. Offsets past the end
+    ///   of the RAM leave no room.
+    pub fn remaining_capacity_from(&self, offset: usize) -> usize {
+        self.ram.len().saturating_sub(offset)
+    }
+
     /// Returns an immutable slice of memory.
     ///
     /// This method is a wrapper around [`slice::get()`].
@@ -127,6 +320,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_set_font() {
+        let mut memory = Memory::try_new().unwrap();
+        memory.protect_font();
+        let font: [u8; 80] = std::array::from_fn(|i| i as u8);
+        memory.set_font(&font);
+        assert_eq!(
+            memory.get(FONT_START_ADDRESS..FONT_START_ADDRESS + 80),
+            Some(font.as_slice())
+        );
+        assert_eq!(memory.high_water_mark(), 0);
+        // The font stays protected against regular writes
+        assert!(memory.write_byte(FONT_START_ADDRESS, 0xFF).is_err());
+    }
+
+    #[test]
+    fn test_try_new_filled() {
+        let memory = Memory::try_new_filled(0xAA).unwrap();
+        assert_eq!(memory.read_byte(0x000), Some(0xAA));
+        assert_eq!(memory.read_byte(RAM_SIZE - 1), Some(0xAA));
+        assert_eq!(
+            memory.get(FONT_START_ADDRESS..FONT_START_ADDRESS + FONT_SET.len()),
+            Some(FONT_SET.as_slice())
+        );
+    }
+
     #[test]
     fn test_read_and_write_byte() {
         let mut memory = Memory::try_new().unwrap();
@@ -157,6 +376,19 @@ mod tests {
         assert_eq!(memory.read_word(0x200), Some(0xABCD));
     }
 
+    #[test]
+    fn test_read_word_endianness() {
+        let mut memory = Memory::try_new().unwrap();
+        let value = [0x12, 0x34];
+        memory.write_at(&value, 0x200).unwrap();
+        assert_eq!(memory.read_word(0x200), Some(0x1234));
+        assert_eq!(memory.read_word_le(0x200), Some(0x3412));
+
+        // Both orders fail on a word straddling the end of memory
+        assert_eq!(memory.read_word(RAM_SIZE - 1), None);
+        assert_eq!(memory.read_word_le(RAM_SIZE - 1), None);
+    }
+
     #[test]
     fn test_write_at() {
         let mut memory = Memory::try_new().unwrap();
@@ -179,6 +411,49 @@ mod tests {
         assert_eq!(memory.read_byte(RAM_SIZE - 5), Some(0x00));
     }
 
+    #[test]
+    fn test_readonly_range() {
+        let mut memory = Memory::try_new().unwrap();
+        memory.set_readonly_range(0x200..0x210);
+
+        assert!(matches!(
+            memory.write_byte(0x205, 0xAB),
+            Err(MemoryError::WriteProtected(0x205))
+        ));
+        // A write overlapping the start of the range is rejected entirely
+        assert!(matches!(
+            memory.write_at(&[1, 2, 3, 4], 0x1FE),
+            Err(MemoryError::WriteProtected(0x200))
+        ));
+        assert_eq!(memory.read_byte(0x1FE), Some(0x00));
+
+        // Writes outside the range still succeed
+        assert!(memory.write_byte(0x210, 0xCD).is_ok());
+        assert!(memory.write_byte(0x1FF, 0xCD).is_ok());
+
+        memory.clear_readonly_ranges();
+        assert!(memory.write_byte(0x205, 0xAB).is_ok());
+        assert_eq!(memory.read_byte(0x205), Some(0xAB));
+    }
+
+    #[test]
+    fn test_protect_font() {
+        let mut memory = Memory::try_new().unwrap();
+        memory.protect_font();
+
+        assert!(matches!(
+            memory.write_byte(FONT_START_ADDRESS, 0x00),
+            Err(MemoryError::WriteProtected(FONT_START_ADDRESS))
+        ));
+        assert!(matches!(
+            memory.write_byte(FONT_START_ADDRESS + 79, 0x00),
+            Err(MemoryError::WriteProtected(_))
+        ));
+        assert_eq!(memory.read_byte(FONT_START_ADDRESS), Some(0xF0));
+        assert!(memory.write_byte(FONT_START_ADDRESS + 80, 0x00).is_ok());
+        assert!(memory.write_byte(FONT_START_ADDRESS - 1, 0x00).is_ok());
+    }
+
     #[test]
     fn test_get() {
         let mut memory = Memory::try_new().unwrap();
@@ -192,4 +467,53 @@ mod tests {
         assert_eq!(memory.get(RAM_SIZE + 1..), None);
         assert_eq!(memory.get(RAM_SIZE - 2..RAM_SIZE + 1), None);
     }
+
+    #[test]
+    fn test_remaining_capacity_from() {
+        let memory = Memory::try_new().unwrap();
+        assert_eq!(memory.remaining_capacity_from(0), RAM_SIZE);
+        assert_eq!(memory.remaining_capacity_from(0x200), RAM_SIZE - 0x200);
+        assert_eq!(memory.remaining_capacity_from(RAM_SIZE - 1), 1);
+        assert_eq!(memory.remaining_capacity_from(RAM_SIZE), 0);
+        assert_eq!(memory.remaining_capacity_from(RAM_SIZE + 1), 0);
+    }
+
+    #[test]
+    fn test_checksum_range() {
+        let mut memory = Memory::try_new().unwrap();
+        // FNV-1a offset basis for an empty range
+        assert_eq!(memory.checksum_range(0x300..0x300), 0x811C_9DC5);
+
+        let before = memory.checksum_range(0x200..0x300);
+        assert_eq!(memory.checksum_range(0x200..0x300), before);
+        memory.write_byte(0x2FF, 0x01).unwrap();
+        assert_ne!(memory.checksum_range(0x200..0x300), before);
+        // Outside the range
+        let after = memory.checksum_range(0x200..0x300);
+        memory.write_byte(0x300, 0x01).unwrap();
+        assert_eq!(memory.checksum_range(0x200..0x300), after);
+
+        assert_eq!(
+            memory.checksum_range(0xF00..0x2000),
+            memory.checksum_range(0xF00..0x1000)
+        );
+    }
+
+    #[test]
+    fn test_high_water_mark() {
+        let mut memory = Memory::try_new().unwrap();
+        assert_eq!(memory.high_water_mark(), 0);
+
+        memory.write_byte(0x500, 0xAA).unwrap();
+        assert_eq!(memory.high_water_mark(), 0x500);
+
+        memory.write_at(&[1, 2, 3], 0x300).unwrap();
+        assert_eq!(memory.high_water_mark(), 0x500);
+        memory.write_at(&[1, 2, 3], 0x600).unwrap();
+        assert_eq!(memory.high_water_mark(), 0x602);
+
+        // Failed writes don't count
+        assert!(memory.write_at(&[0; 4], RAM_SIZE - 2).is_err());
+        assert_eq!(memory.high_water_mark(), 0x602);
+    }
 }
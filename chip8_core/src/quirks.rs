@@ -0,0 +1,43 @@
+//! Configurable behavior differences between CHIP-8 implementations.
+//!
+//! The original COSMAC VIP interpreter and later interpreters (CHIP-48,
+//! SUPER-CHIP) disagree on the semantics of a handful of instructions. ROMs
+//! written for one interpreter may misbehave on another, so these differences
+//! are exposed as "quirks" that can be toggled per machine.
+
+/// Set of behavior toggles for instructions whose semantics differ between
+/// CHIP-8 implementations.
+///
+/// The default matches the behavior of modern interpreters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quirks {
+    /// When `true`, `8XY6` and `8XYE` copy Vy into Vx before shifting, as the
+    /// original COSMAC VIP interpreter did. When `false`, Vx is shifted in
+    /// place and Vy is ignored.
+    pub shift_uses_vy: bool,
+
+    /// Controls the parts of a `DXYN` sprite that extend past the right or
+    /// bottom edge of the display. The sprite origin always wraps (Vx modulo
+    /// the width, Vy modulo the height), so a coordinate computed as a
+    /// "negative" wrapped `u8` lands on screen. When `true`, the pixels past
+    /// the edge are clipped, as on the COSMAC VIP and most interpreters. When
+    /// `false`, they wrap around to the opposite edge, as XO-CHIP does.
+    pub draw_offscreen_clips: bool,
+
+    /// When `true`, `DXYN` waits for the vertical blank, so at most one
+    /// sprite is drawn per 60 Hz frame, as on the COSMAC VIP. Frame loops such
+    /// as [`Chip8::run_frame()`](crate::Chip8::run_frame) honor this quirk;
+    /// [`Chip8::run()`](crate::Chip8::run) has no notion of frames and always
+    /// draws immediately.
+    pub display_wait: bool,
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self {
+            shift_uses_vy: false,
+            draw_offscreen_clips: true,
+            display_wait: false,
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Captured machine state for the CHIP-8 emulator.
+//!
+//! This module provides a plain-data copy of everything that changes while a
+//! program runs (memory, registers, timers, stack, display and keypad). It is
+//! used to record and restore the machine, e.g. for the undo history behind
+//! [`Chip8::step_back()`] or to save and load games.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::Chip8;
+use crate::consts::*;
+use crate::memory::Memory;
+
+/// A copy of the runtime state of a [`Chip8`] machine.
+///
+/// Configuration such as registered hooks or the history depth is not part of
+/// the state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chip8State {
+    memory: Memory,
+    registers: [u8; 16],
+    i: u16,
+    pc: u16,
+    sp: u8,
+    stack: [u16; 16],
+    dt: u8,
+    st: u8,
+    framebuffer: [u8; HIRES_FRAMEBUFFER_WIDTH * HIRES_FRAMEBUFFER_HEIGHT],
+    hires: bool,
+    keyboard: [u8; 16],
+    display_updated: bool,
+    waiting_for_key: Option<usize>,
+    halted: bool,
+    last_draw_collided: bool,
+    collision_count: u64,
+    cycle_count: u64,
+    random_draws: u64,
+    rom_len: usize,
+}
+
+impl Chip8 {
+    /// Creates a machine from a previously captured state.
+    ///
+    /// The machine has the default configuration (see [`Chip8::new()`]) and
+    /// the runtime state of `state`. This is useful to load a saved game into
+    /// a new process.
+    ///
+    /// # Arguments
+    ///
+    /// * `state`: The state captured with [`Chip8::snapshot()`].
+    pub fn from_state(state: Chip8State) -> Self {
+        let mut chip8 = Self::new_infallible();
+        chip8.restore(state);
+        chip8
+    }
+
+    /// Captures the current runtime state of the machine.
+    ///
+    /// The state can later be loaded back with [`Chip8::restore()`] or
+    /// [`Chip8::from_state()`]. Configuration is not captured.
+    pub fn snapshot(&self) -> Chip8State {
+        Chip8State {
+            memory: self.memory.clone(),
+            registers: self.registers,
+            i: self.i,
+            pc: self.pc,
+            sp: self.sp,
+            stack: self.stack,
+            dt: self.dt,
+            st: self.st,
+            framebuffer: self.framebuffer,
+            hires: self.hires,
+            keyboard: self.keyboard,
+            display_updated: self.display_updated,
+            waiting_for_key: self.waiting_for_key,
+            halted: self.halted,
+            last_draw_collided: self.last_draw_collided,
+            collision_count: self.collision_count,
+            cycle_count: self.cycle_count,
+            random_draws: self.random_draws,
+            rom_len: self.rom_len,
+        }
+    }
+
+    /// Hashes the parts of the machine state that a program can observe.
+    ///
+    /// Memory, registers, I, PC, SP, the stack, the timers, the resolution
+    /// and the visible framebuffer are hashed; the random number generator
+    /// and bookkeeping such as the cycle count are not. Two machines in the
+    /// same state hash equally, which is much cheaper to compare than two
+    /// snapshots in determinism tests.
+    ///
+    /// The hash is only stable within one build: don't persist it or compare
+    /// it across Rust versions.
+    pub fn hash_state(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.memory.get(..).hash(&mut hasher);
+        self.registers.hash(&mut hasher);
+        self.i.hash(&mut hasher);
+        self.pc.hash(&mut hasher);
+        self.sp.hash(&mut hasher);
+        self.stack.hash(&mut hasher);
+        self.dt.hash(&mut hasher);
+        self.st.hash(&mut hasher);
+        self.hires.hash(&mut hasher);
+        self.framebuffer().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Replaces the runtime state of the machine with a previously captured one.
+    ///
+    /// Configuration such as quirks and hooks is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `state`: The state captured with [`Chip8::snapshot()`].
+    pub fn restore(&mut self, state: Chip8State) {
+        self.memory = state.memory;
+        self.registers = state.registers;
+        self.i = state.i;
+        self.pc = state.pc;
+        self.sp = state.sp;
+        self.stack = state.stack;
+        self.dt = state.dt;
+        self.st = state.st;
+        self.framebuffer = state.framebuffer;
+        self.hires = state.hires;
+        self.keyboard = state.keyboard;
+        self.display_updated = state.display_updated;
+        self.waiting_for_key = state.waiting_for_key;
+        self.halted = state.halted;
+        self.last_draw_collided = state.last_draw_collided;
+        self.collision_count = state.collision_count;
+        self.cycle_count = state.cycle_count;
+        self.random_draws = state.random_draws;
+        self.rom_len = state.rom_len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_from_state() {
+        // LD V0, 0x42; LD I, 0x300; LD [I], V0; LD ST, V0
+        let mut chip8 = Chip8::with_rom(&[0x60, 0x42, 0xA3, 0x00, 0xF0, 0x55, 0xF0, 0x18]).unwrap();
+        for _ in 0..4 {
+            chip8.run().unwrap();
+        }
+
+        let state = chip8.snapshot();
+        let restored = Chip8::from_state(state.clone());
+        assert_eq!(restored.snapshot(), state);
+        assert_eq!(restored.sound_timer(), 0x42);
+        assert_eq!(restored.cycle_count(), 4);
+    }
+
+    #[test]
+    fn test_hash_state() {
+        // loop: RND V0, 0xFF; LD I, 0x300; LD [I], V0; DRW V0, V0, 1; JP loop
+        let rom = [0xC0, 0xFF, 0xA3, 0x00, 0xF0, 0x55, 0xD0, 0x01, 0x12, 0x00];
+        let mut machines: Vec<Chip8> = (0..2)
+            .map(|_| {
+                let mut chip8 = Chip8::builder().rng_seed(7).build().unwrap();
+                chip8.load_rom(&rom).unwrap();
+                chip8
+            })
+            .collect();
+        assert_eq!(machines[0].hash_state(), machines[1].hash_state());
+
+        for chip8 in &mut machines {
+            for _ in 0..100 {
+                chip8.run().unwrap();
+            }
+        }
+        assert_eq!(machines[0].hash_state(), machines[1].hash_state());
+
+        machines[1].run().unwrap();
+        assert_ne!(machines[0].hash_state(), machines[1].hash_state());
+    }
+}
@@ -0,0 +1,23 @@
+//! Tests for the `test-util` helpers, written against the public API as an
+//! external test harness would use them.
+
+use chip8_core::Chip8;
+
+#[test]
+fn test_draw_sprite_at() {
+    let mut chip8 = Chip8::new().unwrap();
+    // LD V0, 2; LD V1, 3; LD I, 0x050 (font digit 0)
+    chip8
+        .load_rom(&[0x60, 0x02, 0x61, 0x03, 0xA0, 0x50])
+        .unwrap();
+    for _ in 0..3 {
+        chip8.run().unwrap();
+    }
+
+    chip8.draw_sprite_at(0, 1, 5).unwrap();
+
+    let framebuffer = chip8.framebuffer();
+    // Top row of digit 0 is 0xF0: four lit pixels starting at (2, 3)
+    assert_eq!(&framebuffer[3 * 64 + 2..3 * 64 + 7], &[1, 1, 1, 1, 0]);
+    assert!(chip8.is_display_updated());
+}
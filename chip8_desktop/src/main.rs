@@ -2,7 +2,7 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::gui::Framework;
-use chip8_driver::{Driver, DriverError};
+use chip8_driver::{Driver, DriverError, TickResult};
 use error_iter::ErrorIter as _;
 use log::{error, info};
 use pixels::{Error, Pixels, SurfaceTexture};
@@ -20,29 +20,23 @@ pub enum UserCommand {
 
 struct AppState {
     driver: Driver,
-    rom_loaded: bool,
 }
 
 impl AppState {
     fn new() -> Result<Self, DriverError> {
         let driver = Driver::new(500)?;
-        Ok(Self {
-            driver,
-            rom_loaded: false,
-        })
+        Ok(Self { driver })
     }
 
     fn load_rom(&mut self, rom: &[u8]) -> Result<(), DriverError> {
-        if self.rom_loaded {
+        if self.driver.is_rom_loaded() {
             self.driver.reset()?;
-            self.rom_loaded = false;
         }
         self.driver.load_rom(rom)?;
-        self.rom_loaded = true;
         Ok(())
     }
 
-    fn tick(&mut self) -> Result<(), DriverError> {
+    fn tick(&mut self) -> Result<TickResult, DriverError> {
         self.driver.tick()
     }
 }
@@ -109,14 +103,14 @@ fn main() -> Result<(), Error> {
         }
 
         // Update internal state and request a redraw
-        if app.rom_loaded {
-            if let Err(err) = app.tick() {
-                log_error("driver.tick", err);
-                elwt.exit();
-            }
-
-            if app.driver.is_display_updated() {
-                window.request_redraw();
+        if app.driver.is_rom_loaded() {
+            match app.tick() {
+                Ok(result) if result.display_updated => window.request_redraw(),
+                Ok(_) => {}
+                Err(err) => {
+                    log_error("driver.tick", err);
+                    elwt.exit();
+                }
             }
         }
 
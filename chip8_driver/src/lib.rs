@@ -1,42 +1,133 @@
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
-use chip8_core::Chip8;
+use chip8_core::{Chip8, RunStatus};
+
+pub use chip8_core::Quirks;
 
 const TIMER_SPEED_HZ: u64 = 60;
+const DEFAULT_BEEP_FREQUENCY_HZ: f32 = 440.0;
+const CLASSIC_CPU_SPEED_HZ: u64 = 500;
+const SCHIP_CPU_SPEED_HZ: u64 = 1000;
+const FPS_WINDOW_FRAMES: usize = 60;
 
 #[derive(thiserror::Error, Debug)]
 pub enum DriverError {
     #[error(transparent)]
     CoreError(#[from] chip8_core::Chip8Error),
+    #[error("a headless driver has no clock; use frame() or tick_fixed() instead of tick()")]
+    Headless,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorAction {
+    /// Stop the tick and return the error
+    Stop,
+    /// Ignore the error and continue with the next instruction
+    Skip,
+    /// Reset the machine, reload the ROM so the program restarts, and stop the tick
+    Reset,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TickResult {
+    /// Number of instructions executed
+    pub cpu_cycles: u64,
+    /// Number of 60 Hz timer ticks applied
+    pub timer_ticks: u64,
+    /// Whether the display changed since the display updated flag was last cleared
+    pub display_updated: bool,
+}
+
+pub type ErrorHandler = Box<dyn FnMut(&DriverError) -> ErrorAction + Send>;
+
 pub struct Driver {
     core: Chip8,
 
     cpu_speed_hz: u64,
     cpu_cycle_duration: Duration,
-    last_cpu_tick: Instant,
+    // None for headless drivers, which never read the wall clock
+    last_cpu_tick: Option<Instant>,
 
     timer_cycle_duration: Duration,
-    last_timer_tick: Instant,
+    last_timer_tick: Option<Instant>,
+
+    // Kept so the program can be restarted after an ErrorAction::Reset
+    rom: Option<Vec<u8>>,
+
+    beep_frequency_hz: f32,
+    min_beep_frames: u8,
+    beep_frames_left: u8,
+    sound_was_active: bool,
+
+    error_handler: Option<ErrorHandler>,
+
+    // Set after a DXYN with the display_wait quirk; the CPU idles until the next timer tick
+    waiting_for_vblank: bool,
+
+    // Times of the most recent frames, for average_fps()
+    frame_times: VecDeque<Instant>,
+
+    // Timer ticks applied since the last reset, for emulated_time()
+    timer_ticks: u64,
 }
 
 impl Driver {
     pub fn new(cpu_speed_hz: u64) -> Result<Self, DriverError> {
+        Self::with_clock(cpu_speed_hz, Some(Instant::now()))
+    }
+
+    // Only frame() and tick_fixed() can drive a headless driver
+    pub fn headless(cpu_speed_hz: u64) -> Result<Self, DriverError> {
+        Self::with_clock(cpu_speed_hz, None)
+    }
+
+    fn with_clock(cpu_speed_hz: u64, now: Option<Instant>) -> Result<Self, DriverError> {
         let mut driver = Self {
             core: Chip8::new()?,
             cpu_speed_hz,
             cpu_cycle_duration: Duration::from_secs(0),
-            last_cpu_tick: Instant::now(),
+            last_cpu_tick: now,
             timer_cycle_duration: Duration::from_secs_f64(1.0 / TIMER_SPEED_HZ as f64),
-            last_timer_tick: Instant::now(),
+            last_timer_tick: now,
+            rom: None,
+            beep_frequency_hz: DEFAULT_BEEP_FREQUENCY_HZ,
+            min_beep_frames: 0,
+            beep_frames_left: 0,
+            sound_was_active: false,
+            error_handler: None,
+            waiting_for_vblank: false,
+            frame_times: VecDeque::with_capacity(FPS_WINDOW_FRAMES),
+            timer_ticks: 0,
         };
         driver.set_cpu_speed(driver.cpu_speed_hz);
         Ok(driver)
     }
 
+    // Suggests a CPU speed for a ROM: SUPER-CHIP games expect a faster interpreter
+    pub fn suggest_speed(rom: &[u8]) -> u64 {
+        let is_schip = |opcode: u16| {
+            matches!(opcode, 0x00C0..=0x00CF | 0x00FB..=0x00FF)
+                || matches!(opcode & 0xF0FF, 0xF030 | 0xF075 | 0xF085)
+        };
+        if rom
+            .chunks_exact(2)
+            .any(|word| is_schip(u16::from_be_bytes([word[0], word[1]])))
+        {
+            SCHIP_CPU_SPEED_HZ
+        } else {
+            CLASSIC_CPU_SPEED_HZ
+        }
+    }
+
     pub fn reset(&mut self) -> Result<(), DriverError> {
         self.core.reset()?;
+        self.rom = None;
+        self.beep_frames_left = 0;
+        self.sound_was_active = false;
+        self.waiting_for_vblank = false;
+        self.frame_times.clear();
+        self.timer_ticks = 0;
         Ok(())
     }
 
@@ -50,19 +141,47 @@ impl Driver {
         }
     }
 
-    pub fn tick(&mut self) -> Result<(), DriverError> {
-        let now = Instant::now();
-        let cpu_duration = now.duration_since(self.last_cpu_tick);
-        let timer_duration = now.duration_since(self.last_timer_tick);
+    pub fn core(&self) -> &Chip8 {
+        &self.core
+    }
+
+    pub fn cpu_speed(&self) -> u64 {
+        self.cpu_speed_hz
+    }
+
+    pub fn quirks(&self) -> &Quirks {
+        self.core.quirks()
+    }
+
+    pub fn set_quirks(&mut self, quirks: Quirks) {
+        self.core.set_quirks(quirks);
+    }
+
+    pub fn tick(&mut self) -> Result<TickResult, DriverError> {
+        self.tick_at(Instant::now())
+    }
+
+    // Like tick(), with the current time supplied by the caller
+    pub fn tick_at(&mut self, now: Instant) -> Result<TickResult, DriverError> {
+        let (Some(last_cpu_tick), Some(last_timer_tick)) =
+            (self.last_cpu_tick, self.last_timer_tick)
+        else {
+            return Err(DriverError::Headless);
+        };
+        let cpu_duration = now.duration_since(last_cpu_tick);
+        let timer_duration = now.duration_since(last_timer_tick);
+        let mut result = TickResult::default();
 
         // --- CPU Tick ---
         // Check if enough time has passed since the last CPU tick
         if cpu_duration >= self.cpu_cycle_duration {
-            let cycles = cpu_duration.as_nanos() / self.cpu_cycle_duration.as_nanos();
-            for _ in 0..cycles.max(1) {
-                self.core.run()?;
-            }
-            self.last_cpu_tick = now;
+            let cycle_nanos = self.cpu_cycle_duration.as_nanos();
+            let cycles = cpu_duration.as_nanos() / cycle_nanos;
+            result.cpu_cycles = self.run_cpu_cycles(cycles)?;
+            // Carry the partial cycle over to the next tick, otherwise speeds
+            // that don't divide the tick rate lose time on every CPU tick
+            let remainder = Duration::from_nanos((cpu_duration.as_nanos() % cycle_nanos) as u64);
+            self.last_cpu_tick = Some(now - remainder);
         }
 
         // --- Timer Tick ---
@@ -70,12 +189,133 @@ impl Driver {
         if timer_duration >= self.timer_cycle_duration {
             let cycles = timer_duration.as_nanos() / self.timer_cycle_duration.as_nanos();
             for _ in 0..cycles.max(1) {
-                self.core.tick_timers(); // Update timers
+                self.tick_timers(); // Update timers
+                result.timer_ticks += 1;
             }
-            self.last_timer_tick = now;
+            self.last_timer_tick = Some(now);
+            self.record_frame(now);
         }
 
-        Ok(())
+        result.display_updated = self.core.is_display_updated();
+        Ok(result)
+    }
+
+    // Runs `cpu_cycles` instructions followed by one timer tick, without reading the clock
+    pub fn tick_fixed(&mut self, cpu_cycles: u128) -> Result<TickResult, DriverError> {
+        let cpu_cycles = self.run_cpu_cycles(cpu_cycles)?;
+        self.tick_timers();
+        Ok(TickResult {
+            cpu_cycles,
+            timer_ticks: 1,
+            display_updated: self.core.is_display_updated(),
+        })
+    }
+
+    // Runs one 60 Hz frame worth of instructions at the configured CPU speed
+    pub fn frame(&mut self) -> Result<TickResult, DriverError> {
+        self.tick_fixed((self.cpu_speed_hz / TIMER_SPEED_HZ) as u128)
+    }
+
+    // Loads `rom` into a fresh headless driver, runs it and returns the final framebuffer
+    pub fn run_rom_frames(
+        rom: &[u8],
+        frames: usize,
+        cycles_per_frame: usize,
+    ) -> Result<Vec<u8>, DriverError> {
+        let mut driver = Self::headless(cycles_per_frame as u64 * TIMER_SPEED_HZ)?;
+        driver.load_rom(rom)?;
+        for _ in 0..frames {
+            driver.tick_fixed(cycles_per_frame as u128)?;
+        }
+        Ok(driver.framebuffer().to_vec())
+    }
+
+    // A frame is a tick() that advanced the timers
+    fn record_frame(&mut self, now: Instant) {
+        if self.frame_times.len() == FPS_WINDOW_FRAMES {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(now);
+    }
+
+    // Emulated time since the last reset: 1/60 s per timer tick, whether the
+    // ticks came from the wall clock in tick() or from tick_fixed()
+    pub fn emulated_time(&self) -> Duration {
+        Duration::from_nanos(self.timer_ticks * 1_000_000_000 / TIMER_SPEED_HZ)
+    }
+
+    // Frames per real second over the last FPS_WINDOW_FRAMES frames, 0 until two frames ran
+    pub fn average_fps(&self) -> f32 {
+        match (self.frame_times.front(), self.frame_times.back()) {
+            (Some(first), Some(last)) if last > first => {
+                (self.frame_times.len() - 1) as f32 / last.duration_since(*first).as_secs_f32()
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn set_error_handler(&mut self, f: ErrorHandler) {
+        self.error_handler = Some(f);
+    }
+
+    // Returns the number of instructions executed
+    fn run_cpu_cycles(&mut self, cycles: u128) -> Result<u64, DriverError> {
+        let start = self.core.cycle_count();
+        for _ in 0..cycles {
+            if self.waiting_for_vblank {
+                break;
+            }
+            let error = match self.core.step() {
+                Ok(RunStatus::Running) => continue,
+                // Nothing changes until a key is pressed or the machine is reset
+                Ok(RunStatus::WaitingForKey | RunStatus::Halted) => break,
+                Ok(RunStatus::WaitingForVblank) => {
+                    self.waiting_for_vblank = true;
+                    break;
+                }
+                Err(e) => DriverError::from(e),
+            };
+            let action = match self.error_handler.as_mut() {
+                Some(handler) => handler(&error),
+                None => ErrorAction::Stop,
+            };
+            match action {
+                ErrorAction::Stop => return Err(error),
+                ErrorAction::Skip => {}
+                ErrorAction::Reset => {
+                    let executed = self.core.cycle_count() - start;
+                    self.restart()?;
+                    return Ok(executed);
+                }
+            }
+        }
+        Ok(self.core.cycle_count() - start)
+    }
+
+    pub fn tick_with_input(&mut self, keypad: u16) -> Result<TickResult, DriverError> {
+        self.core.set_keyboard_state(keypad);
+        self.tick()
+    }
+
+    fn tick_timers(&mut self) {
+        // A beep starts when the sound timer goes from zero to non-zero
+        if self.core.should_beep() && !self.sound_was_active {
+            self.beep_frames_left = self.min_beep_frames;
+        }
+        self.core.tick_timers();
+        self.timer_ticks += 1;
+        self.waiting_for_vblank = false;
+        self.beep_frames_left = self.beep_frames_left.saturating_sub(1);
+        self.sound_was_active = self.core.should_beep();
+    }
+
+    // Debugging
+    pub fn enable_history(&mut self, depth: usize) {
+        self.core.enable_history(depth);
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        self.core.step_back()
     }
 
     // Input
@@ -87,11 +327,23 @@ impl Driver {
         self.core.key_release(key_index);
     }
 
+    pub fn pressed_keys(&self) -> u16 {
+        self.core.keyboard_state()
+    }
+
     // Output
     pub fn framebuffer(&self) -> &[u8] {
         self.core.framebuffer()
     }
 
+    pub fn width(&self) -> usize {
+        self.core.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.core.height()
+    }
+
     pub fn is_display_updated(&self) -> bool {
         self.core.is_display_updated()
     }
@@ -100,15 +352,52 @@ impl Driver {
         self.core.clear_display_updated_flag();
     }
 
+    pub fn take_display_updated(&mut self) -> bool {
+        self.core.take_display_updated()
+    }
+
     pub fn should_beep(&self) -> bool {
-        self.core.should_beep()
+        self.core.should_beep() || self.beep_frames_left > 0
+    }
+
+    // Audio
+    pub fn set_beep_frequency(&mut self, hz: f32) {
+        self.beep_frequency_hz = hz;
+    }
+
+    pub fn beep_frequency(&self) -> f32 {
+        self.beep_frequency_hz
+    }
+
+    // Keeps short beeps audible for at least `frames` timer ticks (0 = spec-accurate)
+    pub fn set_min_beep_frames(&mut self, frames: u8) {
+        self.min_beep_frames = frames;
+    }
+
+    pub fn min_beep_frames(&self) -> u8 {
+        self.min_beep_frames
     }
 
     // ROM Loading
-    pub fn load_rom(&mut self, rom: &[u8]) -> Result<(), DriverError> {
-        self.core.load_rom(rom)?;
+    pub fn load_rom(&mut self, rom: &[u8]) -> Result<usize, DriverError> {
+        let size = self.core.load_rom(rom)?;
+        self.rom = Some(rom.to_vec());
+        Ok(size)
+    }
+
+    // Resets the machine and reloads the current ROM, if any
+    pub fn restart(&mut self) -> Result<(), DriverError> {
+        let rom = self.rom.take();
+        self.reset()?;
+        if let Some(rom) = rom {
+            self.load_rom(&rom)?;
+        }
         Ok(())
     }
+
+    pub fn is_rom_loaded(&self) -> bool {
+        self.rom.is_some()
+    }
 }
 
 pub fn pixels_width() -> usize {
@@ -118,3 +407,449 @@ pub fn pixels_width() -> usize {
 pub fn pixels_height() -> usize {
     chip8_core::framebuffer_height()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_rom_loaded() {
+        let mut driver = Driver::new(500).unwrap();
+        assert!(!driver.is_rom_loaded());
+
+        assert_eq!(driver.load_rom(&[0x00, 0xE0]).unwrap(), 2);
+        assert!(driver.is_rom_loaded());
+
+        driver.reset().unwrap();
+        assert!(!driver.is_rom_loaded());
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let driver = Driver::new(500).unwrap();
+        assert_eq!(driver.width(), 64);
+        assert_eq!(driver.height(), 32);
+        assert_eq!(driver.framebuffer().len(), driver.width() * driver.height());
+    }
+
+    #[test]
+    fn test_quirks_passthrough() {
+        let mut driver = Driver::new(500).unwrap();
+        assert_eq!(driver.quirks(), &Quirks::default());
+
+        let quirks = Quirks {
+            shift_uses_vy: true,
+            draw_offscreen_clips: false,
+            display_wait: true,
+        };
+        driver.set_quirks(quirks);
+        assert_eq!(driver.quirks(), &quirks);
+        assert_eq!(driver.core().quirks(), &quirks);
+
+        // V1 = 0x04, V0 = V1 >> 1 with the quirk copying V1 first
+        driver.load_rom(&[0x61, 0x04, 0x80, 0x16]).unwrap();
+        driver.run_cpu_cycles(2).unwrap();
+        assert_eq!(driver.core().registers_snapshot()[0], 0x02);
+    }
+
+    #[test]
+    fn test_reset_preserves_configuration() {
+        let quirks = Quirks {
+            shift_uses_vy: true,
+            ..Quirks::default()
+        };
+        let mut driver = Driver::new(500).unwrap();
+        driver.set_min_beep_frames(2);
+        driver.set_cpu_speed(1000);
+        driver.set_quirks(quirks);
+        driver.set_beep_frequency(880.0);
+        driver.load_rom(&[0x00, 0xE0]).unwrap();
+
+        driver.reset().unwrap();
+        assert_eq!(driver.cpu_speed(), 1000);
+        assert_eq!(driver.quirks(), &quirks);
+        assert_eq!(driver.beep_frequency(), 880.0);
+        assert_eq!(driver.min_beep_frames(), 2);
+        assert!(!driver.is_rom_loaded());
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut driver = Driver::new(500).unwrap();
+        // LD V1, 0x01; LD V1, 0x02; LD ST, V1
+        driver
+            .load_rom(&[0x61, 0x01, 0x61, 0x02, 0xF1, 0x18])
+            .unwrap();
+        driver.enable_history(8);
+
+        for _ in 0..3 {
+            driver.run_cpu_cycles(1).unwrap();
+        }
+        assert_eq!(driver.core().sound_timer(), 2);
+
+        assert!(driver.step_back());
+        assert_eq!(driver.core().cycle_count(), 2);
+        assert_eq!(driver.core().sound_timer(), 0);
+    }
+
+    #[test]
+    fn test_pressed_keys() {
+        let mut driver = Driver::new(500).unwrap();
+        assert_eq!(driver.pressed_keys(), 0);
+
+        driver.key_press(0x1);
+        driver.key_press(0xC);
+        assert_eq!(driver.pressed_keys(), (1 << 0x1) | (1 << 0xC));
+
+        driver.key_release(0x1);
+        assert_eq!(driver.pressed_keys(), 1 << 0xC);
+    }
+
+    #[test]
+    fn test_beep_frequency() {
+        let mut driver = Driver::new(500).unwrap();
+        assert_eq!(driver.beep_frequency(), DEFAULT_BEEP_FREQUENCY_HZ);
+
+        driver.set_beep_frequency(880.0);
+        assert_eq!(driver.beep_frequency(), 880.0);
+    }
+
+    #[test]
+    fn test_tick_with_input() {
+        let mut driver = Driver::new(500).unwrap();
+        driver.load_rom(&[0x12, 0x00]).unwrap(); // JP 0x200
+        driver.key_press(0x1);
+
+        driver.tick_with_input(0b0000_0000_1001_0000).unwrap();
+        assert_eq!(driver.core.keyboard_state(), 0b0000_0000_1001_0000);
+    }
+
+    #[test]
+    fn test_error_handler_skip() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut driver = Driver::new(500).unwrap();
+        // Invalid opcode; LD V1, 0x05; LD ST, V1
+        driver
+            .load_rom(&[0xFF, 0xFF, 0x61, 0x05, 0xF1, 0x18])
+            .unwrap();
+
+        let errors = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&errors);
+        driver.set_error_handler(Box::new(move |error| {
+            assert!(matches!(
+                error,
+                DriverError::CoreError(chip8_core::Chip8Error::InvalidOpCode(_))
+            ));
+            counter.fetch_add(1, Ordering::SeqCst);
+            ErrorAction::Skip
+        }));
+
+        driver.run_cpu_cycles(3).unwrap();
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+        assert_eq!(driver.core.sound_timer(), 5);
+    }
+
+    #[test]
+    fn test_error_handler_stop_and_reset() {
+        let mut driver = Driver::new(500).unwrap();
+        driver.load_rom(&[0xFF, 0xFF]).unwrap();
+        assert!(driver.run_cpu_cycles(1).is_err());
+
+        // LD V1, 0x05; invalid opcode
+        driver.set_error_handler(Box::new(|_| ErrorAction::Reset));
+        driver.load_rom(&[0x61, 0x05, 0xFF, 0xFF]).unwrap();
+        assert_eq!(driver.run_cpu_cycles(2).unwrap(), 1);
+        assert!(driver.is_rom_loaded());
+        assert_eq!(driver.core.cycle_count(), 0);
+        assert_eq!(driver.core.registers_snapshot()[1], 0);
+
+        // The program runs again from the start
+        driver.run_cpu_cycles(1).unwrap();
+        assert_eq!(driver.core.registers_snapshot()[1], 0x05);
+    }
+
+    #[test]
+    fn test_cpu_pauses_while_waiting_for_key() {
+        let mut driver = Driver::new(500).unwrap();
+        // LD V0, K; LD V1, 0x05; LD ST, V1; JP 0x206
+        driver
+            .load_rom(&[0xF0, 0x0A, 0x61, 0x05, 0xF1, 0x18, 0x12, 0x06])
+            .unwrap();
+
+        driver.run_cpu_cycles(10).unwrap();
+        assert_eq!(driver.core.cycle_count(), 1);
+
+        driver.key_press(0x2);
+        driver.run_cpu_cycles(10).unwrap();
+        assert_eq!(driver.core.sound_timer(), 5);
+    }
+
+    #[test]
+    fn test_frames_while_waiting_for_key() {
+        let mut driver = Driver::headless(600).unwrap();
+        // LD V1, 0x08; LD DT, V1; LD V0, K; JP 0x206
+        driver
+            .load_rom(&[0x61, 0x08, 0xF1, 0x15, 0xF0, 0x0A, 0x12, 0x06])
+            .unwrap();
+
+        // The first frame runs up to FX0A and stops there
+        assert_eq!(driver.frame().unwrap().cpu_cycles, 3);
+        for _ in 0..5 {
+            let result = driver.frame().unwrap();
+            assert!(result.cpu_cycles <= 1);
+            assert_eq!(result.timer_ticks, 1);
+        }
+        assert_eq!(driver.core().cycle_count(), 3);
+        assert_eq!(driver.core().delay_timer(), 2);
+        assert_eq!(driver.core().pending_key_wait(), Some(0));
+
+        driver.key_press(0x4);
+        assert_eq!(driver.frame().unwrap().cpu_cycles, 10);
+        assert_eq!(driver.core().pending_key_wait(), None);
+    }
+
+    #[test]
+    fn test_min_beep_frames() {
+        let mut driver = Driver::new(500).unwrap();
+        driver.set_min_beep_frames(3);
+        assert_eq!(driver.min_beep_frames(), 3);
+        // LD V1, 0x01; LD ST, V1
+        driver.load_rom(&[0x61, 0x01, 0xF1, 0x18]).unwrap();
+        driver.core.run().unwrap();
+        driver.core.run().unwrap();
+        assert!(driver.should_beep());
+
+        driver.tick_timers();
+        assert_eq!(driver.core.sound_timer(), 0);
+        assert!(driver.should_beep());
+        driver.tick_timers();
+        assert!(driver.should_beep());
+        driver.tick_timers();
+        assert!(!driver.should_beep());
+    }
+
+    #[test]
+    fn test_min_beep_frames_default_is_spec_accurate() {
+        let mut driver = Driver::new(500).unwrap();
+        assert_eq!(driver.min_beep_frames(), 0);
+        driver.load_rom(&[0x61, 0x01, 0xF1, 0x18]).unwrap();
+        driver.core.run().unwrap();
+        driver.core.run().unwrap();
+        assert!(driver.should_beep());
+
+        driver.tick_timers();
+        assert!(!driver.should_beep());
+    }
+
+    #[test]
+    fn test_headless() {
+        let mut driver = Driver::headless(600).unwrap();
+        assert!(matches!(driver.tick(), Err(DriverError::Headless)));
+
+        // LD V1, 0x03; LD ST, V1; ADD V2, 0x01; JP 0x204
+        driver
+            .load_rom(&[0x61, 0x03, 0xF1, 0x18, 0x72, 0x01, 0x12, 0x04])
+            .unwrap();
+        driver.frame().unwrap();
+        assert_eq!(driver.core().cycle_count(), 10);
+        assert_eq!(driver.core().sound_timer(), 2);
+
+        driver.frame().unwrap();
+        assert_eq!(driver.core().cycle_count(), 20);
+        assert_eq!(driver.core().sound_timer(), 1);
+    }
+
+    #[test]
+    fn test_tick_result() {
+        let mut driver = Driver::headless(600).unwrap();
+        // LD V1, 0x03; LD ST, V1; JP 0x204
+        driver
+            .load_rom(&[0x61, 0x03, 0xF1, 0x18, 0x12, 0x04])
+            .unwrap();
+        let result = driver.frame().unwrap();
+        assert_eq!(
+            result,
+            TickResult {
+                cpu_cycles: 10,
+                timer_ticks: 1,
+                display_updated: false,
+            }
+        );
+
+        // LD I, 0x200; DRW V0, V0, 1; JP 0x204
+        driver.reset().unwrap();
+        driver
+            .load_rom(&[0xA2, 0x00, 0xD0, 0x01, 0x12, 0x04])
+            .unwrap();
+        assert!(driver.frame().unwrap().display_updated);
+
+        driver.clear_display_updated_flag();
+        assert!(!driver.frame().unwrap().display_updated);
+    }
+
+    #[test]
+    fn test_run_rom_frames() {
+        // FNV-1a, to keep the expected hash stable across toolchains
+        fn frame_hash(framebuffer: &[u8]) -> u64 {
+            framebuffer
+                .iter()
+                .fold(0xcbf2_9ce4_8422_2325, |hash, &pixel| {
+                    (hash ^ pixel as u64).wrapping_mul(0x0100_0000_01b3)
+                })
+        }
+
+        // LD I, 0x210; LD V0, 0; LD V1, 5; DRW V0, V1, 1
+        // loop: DRW V0, V1, 1; ADD V0, 1; DRW V0, V1, 1; JP loop
+        // 0x210: sprite 0xF0
+        let rom = [
+            0xA2, 0x10, 0x60, 0x00, 0x61, 0x05, 0xD0, 0x11, //
+            0xD0, 0x11, 0x70, 0x01, 0xD0, 0x11, 0x12, 0x08, //
+            0xF0,
+        ];
+        // One frame of 4 cycles moves the sprite one pixel to the right
+        let framebuffer = Driver::run_rom_frames(&rom, 10, 4).unwrap();
+
+        let lit: Vec<usize> = (0..framebuffer.len())
+            .filter(|&index| framebuffer[index] == 1)
+            .collect();
+        assert_eq!(lit, (5 * 64 + 9..5 * 64 + 13).collect::<Vec<_>>());
+        assert_eq!(frame_hash(&framebuffer), 0xa804_feaf_8cd5_09b1);
+    }
+
+    #[test]
+    fn test_suggest_speed() {
+        // CLS; LD V0, 0x05; JP 0x204
+        let classic = [0x00, 0xE0, 0x60, 0x05, 0x12, 0x04];
+        assert_eq!(Driver::suggest_speed(&classic), CLASSIC_CPU_SPEED_HZ);
+
+        // HIGH; LD V0, 0x05; JP 0x204
+        let schip = [0x00, 0xFF, 0x60, 0x05, 0x12, 0x04];
+        assert_eq!(Driver::suggest_speed(&schip), SCHIP_CPU_SPEED_HZ);
+
+        // LD HF, V3
+        assert_eq!(Driver::suggest_speed(&[0xF3, 0x30]), SCHIP_CPU_SPEED_HZ);
+    }
+
+    #[test]
+    fn test_average_fps() {
+        let mut driver = Driver::new(600).unwrap();
+        driver.load_rom(&[0x12, 0x00]).unwrap(); // JP 0x200
+        assert_eq!(driver.average_fps(), 0.0);
+
+        // Present a frame every 1/30 s, i.e. two timer ticks per frame
+        let start = Instant::now();
+        let frame = Duration::from_secs_f64(1.0 / 30.0);
+        for n in 1..=10 {
+            let result = driver.tick_at(start + frame * n).unwrap();
+            assert!(result.timer_ticks >= 1);
+        }
+        let fps = driver.average_fps();
+        assert!((fps - 30.0).abs() < 0.5, "fps = {fps}");
+
+        driver.reset().unwrap();
+        assert_eq!(driver.average_fps(), 0.0);
+    }
+
+    #[test]
+    fn test_tick_at() {
+        let start = Instant::now();
+        let mut driver = Driver::with_clock(500, Some(start)).unwrap();
+        driver.load_rom(&[0x12, 0x00]).unwrap(); // JP 0x200
+
+        // 2 ms per CPU cycle and 16.67 ms per timer tick
+        let now = start + Duration::from_micros(100_001);
+        let result = driver.tick_at(now).unwrap();
+        assert_eq!(result.cpu_cycles, 50);
+        assert_eq!(result.timer_ticks, 6);
+
+        // Less than one CPU cycle later, nothing runs
+        let result = driver.tick_at(now + Duration::from_millis(1)).unwrap();
+        assert_eq!(result.cpu_cycles, 0);
+        assert_eq!(result.timer_ticks, 0);
+    }
+
+    #[test]
+    fn test_emulated_time() {
+        let mut driver = Driver::headless(600).unwrap();
+        driver.load_rom(&[0x12, 0x00]).unwrap();
+        assert_eq!(driver.emulated_time(), Duration::ZERO);
+
+        for _ in 0..120 {
+            driver.tick_fixed(10).unwrap();
+        }
+        assert_eq!(driver.emulated_time(), Duration::from_secs(2));
+
+        driver.frame().unwrap();
+        assert_eq!(
+            driver.emulated_time(),
+            Duration::from_secs(2) + Duration::from_nanos(16_666_666)
+        );
+
+        driver.reset().unwrap();
+        assert_eq!(driver.emulated_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_tick_at_slow_cpu() {
+        let start = Instant::now();
+        let mut driver = Driver::with_clock(10, Some(start)).unwrap();
+        driver.load_rom(&[0x12, 0x00]).unwrap(); // JP 0x200
+
+        // 100 ms per CPU cycle, ticked every 16 ms for one emulated second
+        let mut cpu_cycles = 0;
+        for frame in 1..=63 {
+            let now = start + Duration::from_millis(16 * frame);
+            cpu_cycles += driver.tick_at(now).unwrap().cpu_cycles;
+        }
+        assert_eq!(cpu_cycles, 10);
+        assert_eq!(driver.core().cycle_count(), 10);
+    }
+
+    #[test]
+    fn test_display_wait() {
+        // DRW V0, V1, 1; DRW V0, V1, 1; LD V2, 0x01; JP 0x206
+        let rom = [0xD0, 0x11, 0xD0, 0x11, 0x62, 0x01, 0x12, 0x06];
+        let quirks = Quirks {
+            display_wait: true,
+            ..Quirks::default()
+        };
+
+        let mut driver = Driver::headless(600).unwrap();
+        driver.set_quirks(quirks);
+        driver.load_rom(&rom).unwrap();
+        assert_eq!(driver.frame().unwrap().cpu_cycles, 1);
+        assert_eq!(driver.frame().unwrap().cpu_cycles, 1);
+        assert_eq!(driver.frame().unwrap().cpu_cycles, 10);
+
+        // With the wall clock, the CPU idles until the next timer tick
+        let mut driver = Driver::new(60_000).unwrap();
+        driver.set_quirks(quirks);
+        driver.load_rom(&rom).unwrap();
+        let start = Instant::now();
+        assert_eq!(
+            driver
+                .tick_at(start + Duration::from_millis(5))
+                .unwrap()
+                .cpu_cycles,
+            1
+        );
+        assert_eq!(
+            driver
+                .tick_at(start + Duration::from_millis(10))
+                .unwrap()
+                .cpu_cycles,
+            0
+        );
+        let result = driver.tick_at(start + Duration::from_millis(20)).unwrap();
+        assert_eq!((result.cpu_cycles, result.timer_ticks), (0, 1));
+        assert_eq!(
+            driver
+                .tick_at(start + Duration::from_millis(25))
+                .unwrap()
+                .cpu_cycles,
+            1
+        );
+    }
+}
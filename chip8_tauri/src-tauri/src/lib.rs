@@ -1,5 +1,5 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-use chip8_driver::Driver;
+use chip8_driver::{Driver, Quirks};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use tauri::State;
@@ -19,6 +19,34 @@ pub struct FrameBuffer {
     updated: bool,
 }
 
+/// Serializable mirror of the core `Quirks` for the settings UI.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QuirkSettings {
+    shift_uses_vy: bool,
+    draw_offscreen_clips: bool,
+    display_wait: bool,
+}
+
+impl From<Quirks> for QuirkSettings {
+    fn from(quirks: Quirks) -> Self {
+        Self {
+            shift_uses_vy: quirks.shift_uses_vy,
+            draw_offscreen_clips: quirks.draw_offscreen_clips,
+            display_wait: quirks.display_wait,
+        }
+    }
+}
+
+impl From<QuirkSettings> for Quirks {
+    fn from(settings: QuirkSettings) -> Self {
+        Self {
+            shift_uses_vy: settings.shift_uses_vy,
+            draw_offscreen_clips: settings.draw_offscreen_clips,
+            display_wait: settings.display_wait,
+        }
+    }
+}
+
 #[tauri::command]
 async fn initialize_emulator(
     cpu_speed: u64,
@@ -68,10 +96,7 @@ async fn get_framebuffer(driver_state: State<'_, DriverState>) -> Result<FrameBu
     let mut driver_guard = driver_state.lock().unwrap();
     if let Some(driver) = driver_guard.as_mut() {
         let framebuffer = driver.framebuffer().to_vec();
-        let updated = driver.is_display_updated();
-        if updated {
-            driver.clear_display_updated_flag();
-        }
+        let updated = driver.take_display_updated();
         Ok(FrameBuffer {
             data: framebuffer,
             updated,
@@ -137,6 +162,30 @@ async fn set_cpu_speed(cpu_speed: u64, driver_state: State<'_, DriverState>) ->
     }
 }
 
+#[tauri::command]
+async fn get_quirks(driver_state: State<'_, DriverState>) -> Result<QuirkSettings, String> {
+    let driver_guard = driver_state.lock().unwrap();
+    if let Some(driver) = driver_guard.as_ref() {
+        Ok((*driver.quirks()).into())
+    } else {
+        Err("Emulator not initialized".to_string())
+    }
+}
+
+#[tauri::command]
+async fn set_quirks(
+    quirks: QuirkSettings,
+    driver_state: State<'_, DriverState>,
+) -> Result<(), String> {
+    let mut driver_guard = driver_state.lock().unwrap();
+    if let Some(driver) = driver_guard.as_mut() {
+        driver.set_quirks(quirks.into());
+        Ok(())
+    } else {
+        Err("Emulator not initialized".to_string())
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let driver_state: DriverState = Arc::new(Mutex::new(None));
@@ -155,7 +204,9 @@ pub fn run() {
             key_release,
             should_beep,
             reset_emulator,
-            set_cpu_speed
+            set_cpu_speed,
+            get_quirks,
+            set_quirks
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");